@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::PredictionMarketError;
+use crate::math::{div_round, Rounding};
+
+/// Result of a trade against a YES/NO reserve pair, with the reserves
+/// ordered as (traded outcome, opposing outcome)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Swap {
+    pub shares: u64,
+    pub traded_reserve: u64,
+    pub other_reserve: u64,
+}
+
+/// Buy the traded outcome with `amount_in` collateral (after fees).
+///
+/// The collateral mints complete sets into both reserves, then the bought side
+/// is released until x * y = k holds again, rounded in the pool's favor.
+pub fn buy(traded_reserve: u64, other_reserve: u64, amount_in: u64) -> Result<Swap> {
+    require!(traded_reserve > 0 && other_reserve > 0, PredictionMarketError::NoLiquidity);
+
+    let k = (traded_reserve as u128)
+        .checked_mul(other_reserve as u128)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    let new_other_reserve = other_reserve
+        .checked_add(amount_in)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    let new_traded_reserve = div_round(k, new_other_reserve as u128, Rounding::Up)? as u64;
+    let shares = traded_reserve
+        .checked_add(amount_in)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?
+        .checked_sub(new_traded_reserve)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    Ok(Swap {
+        shares,
+        traded_reserve: new_traded_reserve,
+        other_reserve: new_other_reserve,
+    })
+}
+
+/// Sell the traded outcome for `amount_out` collateral (including fees).
+///
+/// Burning complete sets removes collateral from both reserves; the seller
+/// supplies enough of the sold side to restore x * y = k, rounded up.
+pub fn sell(traded_reserve: u64, other_reserve: u64, amount_out: u64) -> Result<Swap> {
+    require!(amount_out < other_reserve, PredictionMarketError::NoLiquidity);
+
+    let k = (traded_reserve as u128)
+        .checked_mul(other_reserve as u128)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    let new_other_reserve = other_reserve - amount_out;
+    let new_traded_reserve = div_round(k, new_other_reserve as u128, Rounding::Up)?;
+    let new_traded_reserve = u64::try_from(new_traded_reserve)
+        .map_err(|_| PredictionMarketError::ArithmeticOverflow)?;
+    let shares = new_traded_reserve
+        .checked_add(amount_out)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?
+        .checked_sub(traded_reserve)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    Ok(Swap {
+        shares,
+        traded_reserve: new_traded_reserve,
+        other_reserve: new_other_reserve,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn k(traded: u64, other: u64) -> u128 {
+        traded as u128 * other as u128
+    }
+
+    #[test]
+    fn buy_never_decreases_k() {
+        for &(yes, no, amount) in &[
+            (1_000_000, 1_000_000, 1),
+            (1_000_000, 1_000_000, 250_000),
+            (7, 1_000_000_007, 999),
+            (u32::MAX as u64, 3, u32::MAX as u64),
+        ] {
+            let swap = buy(yes, no, amount).unwrap();
+            assert!(k(swap.traded_reserve, swap.other_reserve) >= k(yes, no));
+            // Rounding up leaves the pool at most one share of k slack
+            assert!(k(swap.traded_reserve - 1, swap.other_reserve) < k(yes, no));
+            // Every unit of collateral mints one share on each side
+            assert_eq!(swap.shares + swap.traded_reserve, yes + amount);
+            assert_eq!(swap.other_reserve, no + amount);
+        }
+    }
+
+    #[test]
+    fn sell_never_decreases_k() {
+        for &(yes, no, amount) in &[
+            (1_000_000, 1_000_000, 1),
+            (1_000_000, 1_000_000, 250_000),
+            (1_000_000_007, 7_000, 6_999),
+        ] {
+            let swap = sell(yes, no, amount).unwrap();
+            assert!(k(swap.traded_reserve, swap.other_reserve) >= k(yes, no));
+            assert!(k(swap.traded_reserve - 1, swap.other_reserve) < k(yes, no));
+            assert_eq!(swap.traded_reserve, yes + swap.shares - amount);
+        }
+    }
+
+    #[test]
+    fn sell_cannot_drain_opposing_reserve() {
+        assert!(sell(1_000, 1_000, 1_000).is_err());
+        assert!(buy(0, 1_000, 10).is_err());
+    }
+
+    #[test]
+    fn round_trip_does_not_profit_trader() {
+        let (yes, no) = (5_000_000u64, 3_000_000u64);
+        let bought = buy(yes, no, 400_000).unwrap();
+
+        // Selling back the same collateral costs at least the shares received
+        let sold = sell(bought.traded_reserve, bought.other_reserve, 400_000).unwrap();
+        assert!(sold.shares >= bought.shares);
+        assert!(k(sold.traded_reserve, sold.other_reserve) >= k(yes, no));
+    }
+
+    #[test]
+    fn price_moves_toward_bought_outcome() {
+        // The YES price is the NO reserve's share of total reserves
+        let price = |yes: u64, no: u64| no as u128 * 10000 / (yes as u128 + no as u128);
+
+        let swap = buy(1_000_000, 1_000_000, 100_000).unwrap();
+        assert!(price(swap.traded_reserve, swap.other_reserve) > price(1_000_000, 1_000_000));
+        assert!(swap.shares > 100_000);
+    }
+}
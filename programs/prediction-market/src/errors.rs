@@ -1,4 +1,3 @@
-```rust
 use anchor_lang::prelude::*;
 
 #[error_code]
@@ -537,4 +536,45 @@ pub enum PredictionMarketError {
     #[msg("Red team exercise detected")]
     RedTeamExerciseDetected,
     
-    #[msg("
\ No newline at end of file
+    #[msg("Initial liquidity below minimum")]
+    InsufficientInitialLiquidity,
+    
+    #[msg("Market betting period has ended")]
+    MarketExpired,
+    
+    #[msg("Signer is not the market oracle")]
+    UnauthorizedOracle,
+    
+    #[msg("Market has not been resolved")]
+    MarketNotResolved,
+    
+    #[msg("No winnings to claim")]
+    NoWinnings,
+    
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    
+    #[msg("Market has not been cancelled")]
+    MarketNotCancelled,
+    
+    #[msg("Order book side is full")]
+    OrderBookFull,
+    
+    #[msg("Order not found")]
+    OrderNotFound,
+    
+    #[msg("Position still holds shares that can be sold or claimed")]
+    PositionNotSettled,
+    
+    #[msg("Liquidity pool has not been settled")]
+    PoolNotSettled,
+    
+    #[msg("Liquidity pool has already been settled")]
+    PoolAlreadySettled,
+    
+    #[msg("Owner has orders resting in the order book")]
+    OpenOrdersRemaining,
+    
+    #[msg("Market has not been swept")]
+    MarketNotSwept,
+}
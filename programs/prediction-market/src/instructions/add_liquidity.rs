@@ -1,20 +1,20 @@
-```rust
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::state::*;
 use crate::errors::*;
+use crate::math::{isqrt, mul_div_u64, Rounding};
 
 #[derive(Accounts)]
 pub struct AddLiquidity<'info> {
     #[account(
         mut,
-        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        seeds = [b"market", market.market_id.as_bytes()],
         bump = market.bump,
         has_one = outcome_a_mint,
         has_one = outcome_b_mint,
         constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
     )]
-    pub market: Account<'info, Market>,
+    pub market: Box<Account<'info, Market>>,
 
     #[account(
         mut,
@@ -28,18 +28,20 @@ pub struct AddLiquidity<'info> {
         has_one = outcome_b_vault,
         has_one = lp_token_mint,
     )]
-    pub liquidity_pool: Account<'info, LiquidityPool>,
+    pub liquidity_pool: Box<Account<'info, LiquidityPool>>,
 
     #[account(
-        mut,
+        init_if_needed,
+        payer = liquidity_provider,
+        space = LiquidityPosition::space(),
         seeds = [
-            b"user_liquidity",
+            b"liquidity_position",
             market.key().as_ref(),
             liquidity_provider.key().as_ref(),
         ],
         bump,
     )]
-    pub user_liquidity_position: Account<'info, UserLiquidityPosition>,
+    pub liquidity_position: Box<Account<'info, LiquidityPosition>>,
 
     #[account(mut)]
     pub liquidity_provider: Signer<'info>,
@@ -97,7 +99,7 @@ pub struct AddLiquidity<'info> {
     pub clock: Sysvar<'info, Clock>,
 }
 
-pub fn add_liquidity(
+pub fn handler(
     ctx: Context<AddLiquidity>,
     outcome_a_amount: u64,
     outcome_b_amount: u64,
@@ -105,7 +107,7 @@ pub fn add_liquidity(
 ) -> Result<()> {
     let market = &mut ctx.accounts.market;
     let liquidity_pool = &mut ctx.accounts.liquidity_pool;
-    let user_position = &mut ctx.accounts.user_liquidity_position;
+    let user_position = &mut ctx.accounts.liquidity_position;
     let clock = &ctx.accounts.clock;
 
     // Validate input amounts
@@ -119,47 +121,54 @@ pub fn add_liquidity(
     );
 
     // Calculate current pool reserves
-    let outcome_a_reserve = ctx.accounts.outcome_a_vault.amount;
-    let outcome_b_reserve = ctx.accounts.outcome_b_vault.amount;
-    let total_lp_supply = ctx.accounts.lp_token_mint.supply;
+    let outcome_a_reserve = liquidity_pool.yes_reserves;
+    let outcome_b_reserve = liquidity_pool.no_reserves;
+    let total_lp_supply = liquidity_pool.total_lp_tokens;
+
+    // Initialize the position on first deposit
+    if user_position.owner == Pubkey::default() {
+        user_position.owner = ctx.accounts.liquidity_provider.key();
+        user_position.market = market.key();
+        user_position.pool = liquidity_pool.key();
+        user_position.created_at = clock.unix_timestamp;
+        user_position.bump = ctx.bumps.liquidity_position;
+    }
+
+    if !user_position.is_active {
+        user_position.is_active = true;
+        liquidity_pool.active_providers = liquidity_pool.active_providers
+            .checked_add(1)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    }
 
     // Calculate LP tokens to mint
     let lp_tokens_to_mint = if total_lp_supply == 0 {
         // Initial liquidity provision - use geometric mean
         let initial_liquidity = (outcome_a_amount as u128)
             .checked_mul(outcome_b_amount as u128)
-            .ok_or(PredictionMarketError::MathOverflow)?;
-        
-        // Take square root approximation
-        let mut lp_amount = (initial_liquidity as f64).sqrt() as u64;
-        
-        // Ensure minimum liquidity
-        if lp_amount < 1000 {
-            lp_amount = 1000;
-        }
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
         
-        lp_amount
+        // Exact integer square root, rounded down
+        isqrt(initial_liquidity) as u64
     } else {
         // Subsequent liquidity provision - maintain proportional ratio
-        let lp_from_a = (outcome_a_amount as u128)
-            .checked_mul(total_lp_supply as u128)
-            .ok_or(PredictionMarketError::MathOverflow)?
-            .checked_div(outcome_a_reserve as u128)
-            .ok_or(PredictionMarketError::MathOverflow)? as u64;
-
-        let lp_from_b = (outcome_b_amount as u128)
-            .checked_mul(total_lp_supply as u128)
-            .ok_or(PredictionMarketError::MathOverflow)?
-            .checked_div(outcome_b_reserve as u128)
-            .ok_or(PredictionMarketError::MathOverflow)? as u64;
+        let lp_from_a = mul_div_u64(outcome_a_amount, total_lp_supply, outcome_a_reserve, Rounding::Down)?;
+        let lp_from_b = mul_div_u64(outcome_b_amount, total_lp_supply, outcome_b_reserve, Rounding::Down)?;
 
         // Use the minimum to maintain pool ratio
         std::cmp::min(lp_from_a, lp_from_b)
     };
 
+    // An unseeded pool locks the minimum liquidity out of its first deposit
+    let locked_lp_tokens = if total_lp_supply == 0 { LiquidityPool::MINIMUM_LIQUIDITY } else { 0 };
+    let provider_lp_tokens = lp_tokens_to_mint
+        .checked_sub(locked_lp_tokens)
+        .ok_or(PredictionMarketError::InsufficientInitialLiquidity)?;
+
     // Check slippage protection
+    require!(provider_lp_tokens > 0, PredictionMarketError::InvalidAmount);
     require!(
-        lp_tokens_to_mint >= min_lp_tokens,
+        provider_lp_tokens >= min_lp_tokens,
         PredictionMarketError::SlippageExceeded
     );
 
@@ -203,42 +212,56 @@ pub fn add_liquidity(
         },
         signer_seeds,
     );
-    token::mint_to(mint_ctx, lp_tokens_to_mint)?;
+    token::mint_to(mint_ctx, provider_lp_tokens)?;
 
-    // Update user liquidity position
+    // Update user liquidity position, settling fees earned on the old balance
+    user_position.settle_fees(liquidity_pool)?;
     user_position.lp_tokens = user_position.lp_tokens
-        .checked_add(lp_tokens_to_mint)
-        .ok_or(PredictionMarketError::MathOverflow)?;
+        .checked_add(provider_lp_tokens)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    user_position.sync_fee_debt(liquidity_pool)?;
+
+    user_position.deposited_amount = user_position.deposited_amount
+        .checked_add(outcome_a_amount.checked_add(outcome_b_amount).ok_or(PredictionMarketError::ArithmeticOverflow)?)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
     
     user_position.outcome_a_contributed = user_position.outcome_a_contributed
         .checked_add(outcome_a_amount)
-        .ok_or(PredictionMarketError::MathOverflow)?;
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
     
     user_position.outcome_b_contributed = user_position.outcome_b_contributed
         .checked_add(outcome_b_amount)
-        .ok_or(PredictionMarketError::MathOverflow)?;
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
     
-    user_position.last_update_time = clock.unix_timestamp;
+    user_position.last_updated = clock.unix_timestamp;
 
     // Update liquidity pool stats
     liquidity_pool.total_liquidity = liquidity_pool.total_liquidity
+        .checked_add(outcome_a_amount.checked_add(outcome_b_amount).ok_or(PredictionMarketError::ArithmeticOverflow)?)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    liquidity_pool.total_lp_tokens = liquidity_pool.total_lp_tokens
         .checked_add(lp_tokens_to_mint)
-        .ok_or(PredictionMarketError::MathOverflow)?;
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
     
-    liquidity_pool.outcome_a_reserve = liquidity_pool.outcome_a_reserve
+    liquidity_pool.yes_reserves = liquidity_pool.yes_reserves
         .checked_add(outcome_a_amount)
-        .ok_or(PredictionMarketError::MathOverflow)?;
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
     
-    liquidity_pool.outcome_b_reserve = liquidity_pool.outcome_b_reserve
+    liquidity_pool.no_reserves = liquidity_pool.no_reserves
         .checked_add(outcome_b_amount)
-        .ok_or(PredictionMarketError::MathOverflow)?;
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    liquidity_pool.k_constant = (liquidity_pool.yes_reserves as u128)
+        .checked_mul(liquidity_pool.no_reserves as u128)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
     
-    liquidity_pool.last_update_time = clock.unix_timestamp;
+    liquidity_pool.last_updated = clock.unix_timestamp;
 
     // Update market total liquidity
     market.total_liquidity = market.total_liquidity
-        .checked_add(outcome_a_amount.checked_add(outcome_b_amount).ok_or(PredictionMarketError::MathOverflow)?)
-        .ok_or(PredictionMarketError::MathOverflow)?;
+        .checked_add(outcome_a_amount.checked_add(outcome_b_amount).ok_or(PredictionMarketError::ArithmeticOverflow)?)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
 
     // Emit event
     emit!(LiquidityAddedEvent {
@@ -246,7 +269,7 @@ pub fn add_liquidity(
         liquidity_provider: ctx.accounts.liquidity_provider.key(),
         outcome_a_amount,
         outcome_b_amount,
-        lp_tokens_minted: lp_tokens_to_mint,
+        lp_tokens_minted: provider_lp_tokens,
         timestamp: clock.unix_timestamp,
     });
 
@@ -268,5 +291,4 @@ pub struct LiquidityAddedEvent {
     pub outcome_b_amount: u64,
     pub lp_tokens_minted: u64,
     pub timestamp: i64,
-}
-```
\ No newline at end of file
+}
\ No newline at end of file
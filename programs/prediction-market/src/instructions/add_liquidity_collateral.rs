@@ -0,0 +1,320 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::math::{mul_div_u64, Rounding};
+use crate::invariant::assert_market_vault_solvent;
+
+#[derive(Accounts)]
+pub struct AddLiquidityCollateral<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.paused @ PredictionMarketError::MarketPaused
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        has_one = outcome_a_mint,
+        has_one = outcome_b_mint,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.outcomes.len() == 2 @ PredictionMarketError::InvalidOutcomeCount
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"outcome_tokens", market.key().as_ref()],
+        bump = outcome_tokens.bump,
+        has_one = market
+    )]
+    pub outcome_tokens: Box<Account<'info, OutcomeTokens>>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_pool", market.key().as_ref()],
+        bump = liquidity_pool.bump,
+        has_one = market,
+        has_one = outcome_a_vault,
+        has_one = outcome_b_vault,
+        has_one = lp_token_mint
+    )]
+    pub liquidity_pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(
+        init_if_needed,
+        payer = liquidity_provider,
+        space = LiquidityPosition::space(),
+        seeds = [b"liquidity_position", market.key().as_ref(), liquidity_provider.key().as_ref()],
+        bump
+    )]
+    pub liquidity_position: Box<Account<'info, LiquidityPosition>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub outcome_a_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub outcome_b_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub outcome_a_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub outcome_b_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub lp_token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = provider_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = provider_token_account.owner == liquidity_provider.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub provider_token_account: Account<'info, TokenAccount>,
+
+    /// Receives any outcome A tokens left over after balancing the deposit
+    #[account(
+        mut,
+        constraint = provider_outcome_a_account.mint == outcome_a_mint.key() @ PredictionMarketError::InvalidTokenMint,
+        constraint = provider_outcome_a_account.owner == liquidity_provider.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub provider_outcome_a_account: Account<'info, TokenAccount>,
+
+    /// Receives any outcome B tokens left over after balancing the deposit
+    #[account(
+        mut,
+        constraint = provider_outcome_b_account.mint == outcome_b_mint.key() @ PredictionMarketError::InvalidTokenMint,
+        constraint = provider_outcome_b_account.owner == liquidity_provider.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub provider_outcome_b_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = provider_lp_token_account.mint == lp_token_mint.key() @ PredictionMarketError::InvalidTokenMint,
+        constraint = provider_lp_token_account.owner == liquidity_provider.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub provider_lp_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub liquidity_provider: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddLiquidityCollateral>, amount: u64, min_lp_tokens: u64) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        clock.unix_timestamp < ctx.accounts.market.resolution_time,
+        PredictionMarketError::MarketExpired
+    );
+
+    // Price off the pool's reserves, which include the creator's virtual
+    // seed, rather than the outcome token balances held in the vaults
+    let outcome_a_reserve = ctx.accounts.liquidity_pool.yes_reserves;
+    let outcome_b_reserve = ctx.accounts.liquidity_pool.no_reserves;
+    let total_lp_supply = ctx.accounts.liquidity_pool.total_lp_tokens;
+
+    // `amount` of collateral mints `amount` complete sets. The side with the
+    // larger reserve is deposited in full and the other side is matched to the
+    // pool ratio; the unmatched remainder goes back to the provider.
+    let (outcome_a_amount, outcome_b_amount, lp_tokens_to_mint) = if total_lp_supply == 0 {
+        (amount, amount, amount)
+    } else if outcome_a_reserve >= outcome_b_reserve {
+        let outcome_b_amount = mul_div_u64(amount, outcome_b_reserve, outcome_a_reserve, Rounding::Down)?;
+        let lp_tokens = mul_div_u64(amount, total_lp_supply, outcome_a_reserve, Rounding::Down)?;
+        (amount, outcome_b_amount, lp_tokens)
+    } else {
+        let outcome_a_amount = mul_div_u64(amount, outcome_a_reserve, outcome_b_reserve, Rounding::Down)?;
+        let lp_tokens = mul_div_u64(amount, total_lp_supply, outcome_b_reserve, Rounding::Down)?;
+        (outcome_a_amount, amount, lp_tokens)
+    };
+    let excess_a = amount - outcome_a_amount;
+    let excess_b = amount - outcome_b_amount;
+
+    // An unseeded pool locks the minimum liquidity out of its first deposit
+    let locked_lp_tokens = if total_lp_supply == 0 { LiquidityPool::MINIMUM_LIQUIDITY } else { 0 };
+    let provider_lp_tokens = lp_tokens_to_mint
+        .checked_sub(locked_lp_tokens)
+        .ok_or(PredictionMarketError::InsufficientInitialLiquidity)?;
+
+    require!(provider_lp_tokens > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        provider_lp_tokens >= min_lp_tokens,
+        PredictionMarketError::SlippageExceeded
+    );
+
+    // Lock the collateral backing the complete sets in the market vault
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.provider_token_account.to_account_info(),
+            to: ctx.accounts.market_vault.to_account_info(),
+            authority: ctx.accounts.liquidity_provider.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    // Mint the balanced portion straight into the pool vaults and the excess
+    // to the provider
+    let market_key = ctx.accounts.market.key();
+    let outcome_tokens_seeds = &[
+        b"outcome_tokens",
+        market_key.as_ref(),
+        &[ctx.accounts.outcome_tokens.bump],
+    ];
+    let outcome_tokens_signer = &[&outcome_tokens_seeds[..]];
+
+    let mints = [
+        (&ctx.accounts.outcome_a_mint, &ctx.accounts.outcome_a_vault, outcome_a_amount),
+        (&ctx.accounts.outcome_a_mint, &ctx.accounts.provider_outcome_a_account, excess_a),
+        (&ctx.accounts.outcome_b_mint, &ctx.accounts.outcome_b_vault, outcome_b_amount),
+        (&ctx.accounts.outcome_b_mint, &ctx.accounts.provider_outcome_b_account, excess_b),
+    ];
+    for (mint, to, mint_amount) in mints {
+        if mint_amount == 0 {
+            continue;
+        }
+        let mint_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: ctx.accounts.outcome_tokens.to_account_info(),
+            },
+            outcome_tokens_signer,
+        );
+        token::mint_to(mint_ctx, mint_amount)?;
+    }
+
+    let outcome_tokens = &mut ctx.accounts.outcome_tokens;
+    outcome_tokens.yes_token_supply = outcome_tokens.yes_token_supply
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    outcome_tokens.no_token_supply = outcome_tokens.no_token_supply
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    // Mint LP tokens to provider
+    let pool_seeds = &[
+        b"liquidity_pool",
+        market_key.as_ref(),
+        &[ctx.accounts.liquidity_pool.bump],
+    ];
+    let pool_signer = &[&pool_seeds[..]];
+
+    let mint_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        MintTo {
+            mint: ctx.accounts.lp_token_mint.to_account_info(),
+            to: ctx.accounts.provider_lp_token_account.to_account_info(),
+            authority: ctx.accounts.liquidity_pool.to_account_info(),
+        },
+        pool_signer,
+    );
+    token::mint_to(mint_ctx, provider_lp_tokens)?;
+
+    let market = &mut ctx.accounts.market;
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+    let user_position = &mut ctx.accounts.liquidity_position;
+    let deposited = outcome_a_amount
+        .checked_add(outcome_b_amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    // Initialize the position on first deposit
+    if user_position.owner == Pubkey::default() {
+        user_position.owner = ctx.accounts.liquidity_provider.key();
+        user_position.market = market.key();
+        user_position.pool = liquidity_pool.key();
+        user_position.created_at = clock.unix_timestamp;
+        user_position.bump = ctx.bumps.liquidity_position;
+    }
+
+    if !user_position.is_active {
+        user_position.is_active = true;
+        liquidity_pool.active_providers = liquidity_pool.active_providers
+            .checked_add(1)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    }
+
+    // Update user liquidity position, settling fees earned on the old balance
+    user_position.settle_fees(liquidity_pool)?;
+    user_position.lp_tokens = user_position.lp_tokens
+        .checked_add(provider_lp_tokens)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    user_position.sync_fee_debt(liquidity_pool)?;
+
+    user_position.deposited_amount = user_position.deposited_amount
+        .checked_add(deposited)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    user_position.outcome_a_contributed = user_position.outcome_a_contributed
+        .checked_add(outcome_a_amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    user_position.outcome_b_contributed = user_position.outcome_b_contributed
+        .checked_add(outcome_b_amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    user_position.last_updated = clock.unix_timestamp;
+
+    // Update liquidity pool stats
+    liquidity_pool.total_liquidity = liquidity_pool.total_liquidity
+        .checked_add(deposited)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    liquidity_pool.total_lp_tokens = liquidity_pool.total_lp_tokens
+        .checked_add(lp_tokens_to_mint)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    liquidity_pool.yes_reserves = liquidity_pool.yes_reserves
+        .checked_add(outcome_a_amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    liquidity_pool.no_reserves = liquidity_pool.no_reserves
+        .checked_add(outcome_b_amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    liquidity_pool.k_constant = (liquidity_pool.yes_reserves as u128)
+        .checked_mul(liquidity_pool.no_reserves as u128)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    liquidity_pool.last_updated = clock.unix_timestamp;
+
+    market.total_liquidity = market.total_liquidity
+        .checked_add(deposited)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(CollateralLiquidityAddedEvent {
+        market: market.key(),
+        liquidity_provider: ctx.accounts.liquidity_provider.key(),
+        collateral_amount: amount,
+        outcome_a_amount,
+        outcome_b_amount,
+        excess_a,
+        excess_b,
+        lp_tokens_minted: provider_lp_tokens,
+        timestamp: clock.unix_timestamp,
+    });
+
+    assert_market_vault_solvent(&ctx.accounts.market, Some(&ctx.accounts.outcome_tokens), &mut ctx.accounts.market_vault)?;
+
+    Ok(())
+}
+
+#[event]
+pub struct CollateralLiquidityAddedEvent {
+    pub market: Pubkey,
+    pub liquidity_provider: Pubkey,
+    pub collateral_amount: u64,
+    pub outcome_a_amount: u64,
+    pub outcome_b_amount: u64,
+    pub excess_a: u64,
+    pub excess_b: u64,
+    pub lp_tokens_minted: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::*;
+use crate::invariant::{accrued_fees, market_liabilities, pool_liabilities, MarketLiabilities};
+
+#[derive(Accounts)]
+pub struct AuditMarket<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        seeds = [b"outcome_tokens", market.key().as_ref()],
+        bump = outcome_tokens.bump,
+        has_one = market
+    )]
+    pub outcome_tokens: Box<Account<'info, OutcomeTokens>>,
+
+    #[account(
+        seeds = [b"liquidity_pool", market.key().as_ref()],
+        bump = liquidity_pool.bump,
+        has_one = market,
+        has_one = liquidity_vault
+    )]
+    pub liquidity_pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    pub liquidity_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"protocol_fee", market.key().as_ref()],
+        bump,
+        token::mint = market.token_mint
+    )]
+    pub protocol_fee_account: Account<'info, TokenAccount>,
+}
+
+/// Balances and liabilities of every vault a market holds collateral in
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MarketAudit {
+    pub market_vault_balance: u64,
+    pub market_liabilities: MarketLiabilities,
+    pub liquidity_vault_balance: u64,
+    pub pool_liabilities: u64,
+    pub protocol_fee_balance: u64,
+    pub accrued_fees: u64,
+    pub solvent: bool,
+}
+
+pub fn handler(ctx: Context<AuditMarket>) -> Result<MarketAudit> {
+    let market = &ctx.accounts.market;
+
+    let liabilities = market_liabilities(market, Some(&ctx.accounts.outcome_tokens))?;
+    let pool_liabilities = pool_liabilities(market, &ctx.accounts.liquidity_pool)?;
+    let accrued_fees = accrued_fees(market)?;
+
+    let market_vault_balance = ctx.accounts.market_vault.amount;
+    let liquidity_vault_balance = ctx.accounts.liquidity_vault.amount;
+    let protocol_fee_balance = ctx.accounts.protocol_fee_account.amount;
+
+    let solvent = market_vault_balance >= liabilities.total()?
+        && liquidity_vault_balance >= pool_liabilities
+        && protocol_fee_balance >= accrued_fees;
+
+    msg!(
+        "Audit {}: market vault {} / {}, liquidity vault {} / {}, fees {} / {}",
+        market.market_id,
+        market_vault_balance,
+        liabilities.total()?,
+        liquidity_vault_balance,
+        pool_liabilities,
+        protocol_fee_balance,
+        accrued_fees
+    );
+
+    Ok(MarketAudit {
+        market_vault_balance,
+        market_liabilities: liabilities,
+        liquidity_vault_balance,
+        pool_liabilities,
+        protocol_fee_balance,
+        accrued_fees,
+        solvent,
+    })
+}
@@ -0,0 +1,226 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::cpmm;
+use crate::math::{apply_bps, Rounding};
+use crate::invariant::assert_pool_vault_solvent;
+
+#[derive(Accounts)]
+#[instruction(outcome: u8)]
+pub struct BuyOutcome<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.paused @ PredictionMarketError::MarketPaused
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = Clock::get()?.unix_timestamp < market.end_time @ PredictionMarketError::MarketExpired,
+        constraint = market.outcomes.len() == 2 @ PredictionMarketError::InvalidOutcomeCount,
+        constraint = outcome < 2 @ PredictionMarketError::InvalidOutcome
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_stats", market.key().as_ref()],
+        bump = market_stats.bump
+    )]
+    pub market_stats: Box<Account<'info, MarketStats>>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_pool", market.key().as_ref()],
+        bump = liquidity_pool.bump,
+        has_one = market,
+        has_one = liquidity_vault
+    )]
+    pub liquidity_pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(mut)]
+    pub liquidity_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = trader,
+        space = Position::space(),
+        seeds = [b"amm_position", market.key().as_ref(), trader.key().as_ref(), &[outcome]],
+        bump
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(
+        mut,
+        constraint = trader_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = trader_token_account.owner == trader.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub trader_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub trader: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<BuyOutcome>, outcome: u8, amount: u64, min_shares_out: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let market_stats = &mut ctx.accounts.market_stats;
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+    let position = &mut ctx.accounts.position;
+    let clock = Clock::get()?;
+
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        liquidity_pool.yes_reserves > 0 && liquidity_pool.no_reserves > 0,
+        PredictionMarketError::NoLiquidity
+    );
+
+    // Pool fee is taken from the collateral before it enters the curve
+    let fee = apply_bps(amount, liquidity_pool.fee_rate_bps as u64, Rounding::Up)?;
+    let amount_after_fee = amount
+        .checked_sub(fee)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    let (bought_reserve, other_reserve) = if outcome == 0 {
+        (liquidity_pool.yes_reserves, liquidity_pool.no_reserves)
+    } else {
+        (liquidity_pool.no_reserves, liquidity_pool.yes_reserves)
+    };
+    let swap = cpmm::buy(bought_reserve, other_reserve, amount_after_fee)?;
+    let shares_out = swap.shares;
+
+    require!(shares_out > 0, PredictionMarketError::InvalidAmount);
+    require!(shares_out >= min_shares_out, PredictionMarketError::SlippageExceeded);
+
+    // Transfer collateral from trader to the pool
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.trader_token_account.to_account_info(),
+            to: ctx.accounts.liquidity_vault.to_account_info(),
+            authority: ctx.accounts.trader.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    // Update pool reserves
+    if outcome == 0 {
+        liquidity_pool.yes_reserves = swap.traded_reserve;
+        liquidity_pool.no_reserves = swap.other_reserve;
+    } else {
+        liquidity_pool.no_reserves = swap.traded_reserve;
+        liquidity_pool.yes_reserves = swap.other_reserve;
+    }
+    liquidity_pool.k_constant = (liquidity_pool.yes_reserves as u128)
+        .checked_mul(liquidity_pool.no_reserves as u128)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    let outstanding = liquidity_pool.shares_outstanding_mut(outcome);
+    *outstanding = outstanding
+        .checked_add(shares_out)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    liquidity_pool.accrue_lp_fees(fee)?;
+    liquidity_pool.update_yield_metrics(clock.unix_timestamp);
+    liquidity_pool.last_updated = clock.unix_timestamp;
+
+    // Initialize or update position
+    if position.owner == Pubkey::default() {
+        position.owner = ctx.accounts.trader.key();
+        position.market = market.key();
+        position.outcome_id = outcome;
+        position.created_at = clock.unix_timestamp;
+        position.bump = ctx.bumps.position;
+    }
+
+    position.add_shares(shares_out, amount)?;
+    position.last_updated = clock.unix_timestamp;
+
+    // Update prices and stats
+    update_pool_prices(liquidity_pool, market_stats, outcome)?;
+
+    market_stats.total_volume = market_stats.total_volume
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    if outcome == 0 {
+        market_stats.yes_volume = market_stats.yes_volume
+            .checked_add(amount)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    } else {
+        market_stats.no_volume = market_stats.no_volume
+            .checked_add(amount)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    }
+
+    market_stats.total_trades = market_stats.total_trades
+        .checked_add(1)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    market.total_volume = market.total_volume
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.total_volume = global_state.total_volume
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(OutcomeBoughtEvent {
+        market: market.key(),
+        trader: ctx.accounts.trader.key(),
+        outcome,
+        amount,
+        fee,
+        shares_out,
+        yes_price: market_stats.yes_price,
+        no_price: market_stats.no_price,
+        timestamp: clock.unix_timestamp,
+    });
+
+    assert_pool_vault_solvent(&ctx.accounts.market, &ctx.accounts.liquidity_pool, &mut ctx.accounts.liquidity_vault)?;
+
+    Ok(())
+}
+
+/// Derive YES/NO prices in basis points from pool reserves; the price of an
+/// outcome is the opposing reserve's share of total reserves
+pub fn update_pool_prices(
+    liquidity_pool: &LiquidityPool,
+    market_stats: &mut MarketStats,
+    traded_outcome: u8,
+) -> Result<()> {
+    let total_reserves = (liquidity_pool.yes_reserves as u128)
+        .checked_add(liquidity_pool.no_reserves as u128)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    require!(total_reserves > 0, PredictionMarketError::NoLiquidity);
+
+    market_stats.yes_price = (liquidity_pool.no_reserves as u128 * 10000 / total_reserves) as u64;
+    market_stats.no_price = 10000 - market_stats.yes_price;
+    market_stats.last_trade_price = if traded_outcome == 0 {
+        market_stats.yes_price
+    } else {
+        market_stats.no_price
+    };
+
+    Ok(())
+}
+
+#[event]
+pub struct OutcomeBoughtEvent {
+    pub market: Pubkey,
+    pub trader: Pubkey,
+    pub outcome: u8,
+    pub amount: u64,
+    pub fee: u64,
+    pub shares_out: u64,
+    pub yes_price: u64,
+    pub no_price: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,208 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::lmsr;
+use crate::utils::{accrue_fees, calculate_trading_fees};
+use crate::invariant::{assert_fee_account_solvent, assert_market_vault_solvent};
+
+#[derive(Accounts)]
+#[instruction(outcome: u8)]
+pub struct BuyShares<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.paused @ PredictionMarketError::MarketPaused
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = Clock::get()?.unix_timestamp < market.end_time @ PredictionMarketError::MarketExpired,
+        constraint = outcome < market.outcomes.len() as u8 @ PredictionMarketError::InvalidOutcome,
+        constraint = market.lmsr_b > 0 @ PredictionMarketError::FeatureNotEnabled
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_stats", market.key().as_ref()],
+        bump = market_stats.bump
+    )]
+    pub market_stats: Box<Account<'info, MarketStats>>,
+
+    #[account(
+        init_if_needed,
+        payer = trader,
+        space = Position::space(),
+        seeds = [b"position", market.key().as_ref(), trader.key().as_ref(), &[outcome]],
+        bump
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(
+        mut,
+        constraint = trader_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = trader_token_account.owner == trader.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub trader_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_fee", market.key().as_ref()],
+        bump,
+        token::mint = market.token_mint
+    )]
+    pub protocol_fee_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub trader: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<BuyShares>, outcome: u8, shares: u64, max_cost: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let market_stats = &mut ctx.accounts.market_stats;
+    let position = &mut ctx.accounts.position;
+    let clock = Clock::get()?;
+    let index = outcome as usize;
+
+    require!(shares > 0, PredictionMarketError::InvalidAmount);
+
+    // Quote the trade against the LMSR cost function
+    let quantities: Vec<u64> = market.outcomes.iter().map(|o| o.total_shares).collect();
+    let cost = lmsr::buy_cost(&quantities, market.lmsr_b, index, shares)?;
+
+    require!(cost > 0, PredictionMarketError::InvalidAmount);
+
+    // Trading fees are paid on top of the LMSR cost
+    let (creator_fee, platform_fee, oracle_fee) = calculate_trading_fees(market, cost)?;
+    let fee = accrue_fees(market, creator_fee, platform_fee, oracle_fee)?;
+    require!(
+        cost.checked_add(fee).ok_or(PredictionMarketError::ArithmeticOverflow)? <= max_cost,
+        PredictionMarketError::SlippageExceeded
+    );
+
+    // Transfer collateral from trader to market vault
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.trader_token_account.to_account_info(),
+            to: ctx.accounts.market_vault.to_account_info(),
+            authority: ctx.accounts.trader.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, cost)?;
+
+    if fee > 0 {
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.trader_token_account.to_account_info(),
+                to: ctx.accounts.protocol_fee_account.to_account_info(),
+                authority: ctx.accounts.trader.to_account_info(),
+            },
+        );
+        token::transfer(transfer_ctx, fee)?;
+    }
+
+    // Initialize or update position
+    if position.owner == Pubkey::default() {
+        position.owner = ctx.accounts.trader.key();
+        position.market = market.key();
+        position.outcome_id = outcome;
+        position.created_at = clock.unix_timestamp;
+        position.bump = ctx.bumps.position;
+    }
+
+    // Average price in basis points per share, weighted by shares held
+    position.add_shares(shares, cost)?;
+    position.last_updated = clock.unix_timestamp;
+
+    // Update outcome quantities and prices
+    market.outcomes[index].total_shares = market.outcomes[index]
+        .total_shares
+        .checked_add(shares)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    market.outcomes[index].volume_24h = market.outcomes[index]
+        .volume_24h
+        .checked_add(cost)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    update_prices(market)?;
+
+    // Update market totals
+    market.total_volume = market.total_volume
+        .checked_add(cost)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.total_volume = global_state.total_volume
+        .checked_add(cost)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    market_stats.total_volume = market_stats.total_volume
+        .checked_add(cost)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    market_stats.total_trades = market_stats.total_trades
+        .checked_add(1)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    market_stats.last_trade_price = market.outcomes[index].price;
+
+    emit!(SharesBoughtEvent {
+        market: market.key(),
+        trader: ctx.accounts.trader.key(),
+        outcome,
+        shares,
+        cost,
+        fee,
+        new_price: market.outcomes[index].price,
+        timestamp: clock.unix_timestamp,
+    });
+
+    assert_market_vault_solvent(&ctx.accounts.market, None, &mut ctx.accounts.market_vault)?;
+    assert_fee_account_solvent(&ctx.accounts.market, &mut ctx.accounts.protocol_fee_account)?;
+
+    Ok(())
+}
+
+/// Refresh every outcome's `price`/`last_price` from the LMSR quantities
+pub fn update_prices(market: &mut Market) -> Result<()> {
+    let quantities: Vec<u64> = market.outcomes.iter().map(|o| o.total_shares).collect();
+    let prices = lmsr::prices(&quantities, market.lmsr_b)?;
+
+    for (outcome_data, price) in market.outcomes.iter_mut().zip(prices) {
+        outcome_data.last_price = outcome_data.price;
+        outcome_data.price = price;
+    }
+
+    Ok(())
+}
+
+#[event]
+pub struct SharesBoughtEvent {
+    pub market: Pubkey,
+    pub trader: Pubkey,
+    pub outcome: u8,
+    pub shares: u64,
+    pub cost: u64,
+    pub fee: u64,
+    pub new_price: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,248 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::utils::recalculate_outcome_odds;
+use crate::math::{apply_bps, Rounding};
+use crate::invariant::{assert_market_vault_solvent, assert_pool_vault_solvent};
+
+#[derive(Accounts)]
+#[instruction(outcome: u8)]
+pub struct CancelBet<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.paused @ PredictionMarketError::MarketPaused
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = Clock::get()?.unix_timestamp < market.end_time @ PredictionMarketError::MarketExpired,
+        constraint = outcome < market.outcomes.len() as u8 @ PredictionMarketError::InvalidOutcome
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_stats", market.key().as_ref()],
+        bump = market_stats.bump
+    )]
+    pub market_stats: Box<Account<'info, MarketStats>>,
+
+    #[account(
+        mut,
+        seeds = [b"bet", market.key().as_ref(), bettor.key().as_ref()],
+        bump = bet.bump,
+        constraint = bet.bettor == bettor.key() @ PredictionMarketError::NoPosition
+    )]
+    pub bet: Box<Account<'info, Bet>>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_pool", market.key().as_ref()],
+        bump = liquidity_pool.bump,
+        has_one = liquidity_vault
+    )]
+    pub liquidity_pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(mut)]
+    pub liquidity_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = bettor_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = bettor_token_account.owner == bettor.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub bettor_token_account: Account<'info, TokenAccount>,
+
+    pub bettor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<CancelBet>, outcome: u8, amount: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let market_stats = &mut ctx.accounts.market_stats;
+    let bet = &mut ctx.accounts.bet;
+    let clock = Clock::get()?;
+    let index = outcome as usize;
+
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+
+    let bet_outcome = bet.outcomes.get(index).ok_or(PredictionMarketError::NoPosition)?;
+    let staked = bet_outcome.amount;
+    require!(staked > 0, PredictionMarketError::NoPosition);
+    require!(amount <= staked, PredictionMarketError::InsufficientFunds);
+
+    // Stake placed in the outcome's current window can be cancelled until the
+    // window closes, and any stake at any time before the market's lock
+    let window_end = bet_outcome.cancellable_since
+        .checked_add(market.cancellation_window)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    let within_window = clock.unix_timestamp <= window_end
+        && amount <= bet_outcome.cancellable_amount;
+    let before_lock = clock.unix_timestamp < market.cancellation_lock_time;
+    require!(within_window || before_lock, PredictionMarketError::MarketLocked);
+
+    let cancellation_fee = apply_bps(amount, market.cancellation_fee_bps as u64, Rounding::Up)?;
+    let refund_amount = amount
+        .checked_sub(cancellation_fee)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    let seeds = &[
+        b"market",
+        market.market_id.as_bytes(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Refund the bettor net of the cancellation fee
+    if refund_amount > 0 {
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.market_vault.to_account_info(),
+                to: ctx.accounts.bettor_token_account.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, refund_amount)?;
+    }
+
+    // Pay the cancellation fee to the liquidity pool
+    if cancellation_fee > 0 {
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.market_vault.to_account_info(),
+                to: ctx.accounts.liquidity_vault.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, cancellation_fee)?;
+
+        let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+        liquidity_pool.accrue_lp_fees(cancellation_fee)?;
+        liquidity_pool.last_updated = clock.unix_timestamp;
+    }
+
+    // Revert the bet
+    bet.outcomes[index].amount = staked
+        .checked_sub(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    // Cancellations take back the most recent stake first
+    bet.outcomes[index].cancellable_amount = bet.outcomes[index]
+        .cancellable_amount
+        .saturating_sub(amount);
+
+    bet.total_amount = bet.total_amount
+        .checked_sub(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    // Revert market outcome totals
+    market.outcomes[index].total_amount = market.outcomes[index]
+        .total_amount
+        .checked_sub(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    // A fully cancelled outcome takes back every bet placed on it
+    if bet.outcomes[index].amount == 0 {
+        let bet_count = bet.outcomes[index].bet_count;
+        bet.outcomes[index].bet_count = 0;
+
+        market.outcomes[index].bet_count = market.outcomes[index]
+            .bet_count
+            .checked_sub(bet_count)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+        market.total_bets = market.total_bets
+            .checked_sub(bet_count)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+        market_stats.total_trades = market_stats.total_trades
+            .checked_sub(bet_count)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    }
+
+    if bet.total_amount == 0 {
+        market_stats.unique_traders = market_stats.unique_traders
+            .checked_sub(1)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    }
+
+    market.total_volume = market.total_volume
+        .checked_sub(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.total_volume = global_state.total_volume
+        .checked_sub(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    // Revert market stats
+    market_stats.total_volume = market_stats.total_volume
+        .checked_sub(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    match outcome {
+        0 => {
+            market_stats.yes_volume = market_stats.yes_volume
+                .checked_sub(amount)
+                .ok_or(PredictionMarketError::ArithmeticOverflow)?
+        }
+        1 => {
+            market_stats.no_volume = market_stats.no_volume
+                .checked_sub(amount)
+                .ok_or(PredictionMarketError::ArithmeticOverflow)?
+        }
+        _ => {}
+    }
+
+    // Calculate new odds for all outcomes
+    recalculate_outcome_odds(&mut market.outcomes);
+
+    emit!(BetCancelledEvent {
+        market: market.key(),
+        bettor: ctx.accounts.bettor.key(),
+        outcome,
+        amount,
+        cancellation_fee,
+        refund_amount,
+        remaining_amount: bet.outcomes[index].amount,
+        timestamp: clock.unix_timestamp,
+        total_market_volume: market.total_volume,
+    });
+
+    assert_market_vault_solvent(&ctx.accounts.market, None, &mut ctx.accounts.market_vault)?;
+    assert_pool_vault_solvent(&ctx.accounts.market, &ctx.accounts.liquidity_pool, &mut ctx.accounts.liquidity_vault)?;
+
+    Ok(())
+}
+
+#[event]
+pub struct BetCancelledEvent {
+    pub market: Pubkey,
+    pub bettor: Pubkey,
+    pub outcome: u8,
+    pub amount: u64,
+    pub cancellation_fee: u64,
+    pub refund_amount: u64,
+    pub remaining_amount: u64,
+    pub timestamp: i64,
+    pub total_market_volume: u64,
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct CancelMarket<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        constraint = authority.key() == market.creator || authority.key() == market.oracle
+            @ PredictionMarketError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelMarket>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp < market.resolution_time,
+        PredictionMarketError::MarketExpired
+    );
+
+    // Bettors recover their stakes through refund_bet; positions, outcome
+    // tokens and LP shares are refunded at the Invalid split
+    market.status = MarketStatus::Cancelled;
+    market.open_claims(clock.unix_timestamp);
+
+    emit!(MarketCancelledEvent {
+        market: market.key(),
+        cancelled_by: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MarketCancelledEvent {
+    pub market: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(outcome: u8)]
+pub struct CancelOrder<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"order_book", market.key().as_ref(), &[outcome]],
+        bump = order_book.bump,
+        has_one = market,
+        has_one = book_vault
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+
+    #[account(mut)]
+    pub book_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), owner.key().as_ref(), &[outcome]],
+        bump = position.bump,
+        constraint = position.owner == owner.key() @ PredictionMarketError::NoPosition
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = owner_token_account.owner == owner.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<CancelOrder>, outcome: u8, order_id: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let order_book = &mut ctx.accounts.order_book;
+    let position = &mut ctx.accounts.position;
+    let owner = ctx.accounts.owner.key();
+    let clock = Clock::get()?;
+
+    let (order, side) = if let Some(index) = order_book.bids.iter().position(|o| o.order_id == order_id) {
+        (order_book.bids.remove(index), TradeType::Buy)
+    } else if let Some(index) = order_book.asks.iter().position(|o| o.order_id == order_id) {
+        (order_book.asks.remove(index), TradeType::Sell)
+    } else {
+        return err!(PredictionMarketError::OrderNotFound);
+    };
+    require!(order.owner == owner, PredictionMarketError::InvalidAuthority);
+
+    match side {
+        // Release escrowed collateral
+        TradeType::Buy => {
+            if order.collateral_locked > 0 {
+                let market_key = market.key();
+                let seeds = &[
+                    b"order_book",
+                    market_key.as_ref(),
+                    &[outcome],
+                    &[order_book.bump],
+                ];
+                let signer_seeds = &[&seeds[..]];
+
+                let transfer_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.book_vault.to_account_info(),
+                        to: ctx.accounts.owner_token_account.to_account_info(),
+                        authority: order_book.to_account_info(),
+                    },
+                    signer_seeds,
+                );
+                token::transfer(transfer_ctx, order.collateral_locked)?;
+            }
+        }
+        // Return locked shares to the position
+        TradeType::Sell => {
+            position.shares = position.shares
+                .checked_add(order.quantity)
+                .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+            position.last_updated = clock.unix_timestamp;
+        }
+    }
+
+    emit!(OrderCancelledEvent {
+        market: market.key(),
+        outcome,
+        order_id,
+        owner,
+        side,
+        remaining_quantity: order.quantity,
+        refunded_collateral: order.collateral_locked,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct OrderCancelledEvent {
+    pub market: Pubkey,
+    pub outcome: u8,
+    pub order_id: u64,
+    pub owner: Pubkey,
+    pub side: TradeType,
+    pub remaining_quantity: u64,
+    pub refunded_collateral: u64,
+    pub timestamp: i64,
+}
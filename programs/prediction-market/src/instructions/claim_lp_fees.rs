@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::invariant::assert_pool_vault_solvent;
+
+#[derive(Accounts)]
+pub struct ClaimLpFees<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_pool", market.key().as_ref()],
+        bump = liquidity_pool.bump,
+        has_one = market,
+        has_one = liquidity_vault
+    )]
+    pub liquidity_pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_position", market.key().as_ref(), liquidity_provider.key().as_ref()],
+        bump = liquidity_position.bump,
+        constraint = liquidity_position.owner == liquidity_provider.key() @ PredictionMarketError::NoPosition,
+        has_one = market
+    )]
+    pub liquidity_position: Box<Account<'info, LiquidityPosition>>,
+
+    #[account(mut)]
+    pub liquidity_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = provider_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = provider_token_account.owner == liquidity_provider.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub provider_token_account: Account<'info, TokenAccount>,
+
+    pub liquidity_provider: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<ClaimLpFees>) -> Result<()> {
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+    let liquidity_position = &mut ctx.accounts.liquidity_position;
+    let clock = Clock::get()?;
+
+    // Bring the position up to date with the pool's fee accumulator
+    liquidity_position.settle_fees(liquidity_pool)?;
+
+    let amount = liquidity_position.earned_fees;
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+
+    let market_key = ctx.accounts.market.key();
+    let seeds = &[
+        b"liquidity_pool",
+        market_key.as_ref(),
+        &[liquidity_pool.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.liquidity_vault.to_account_info(),
+            to: ctx.accounts.provider_token_account.to_account_info(),
+            authority: liquidity_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    liquidity_position.earned_fees = 0;
+    liquidity_position.last_updated = clock.unix_timestamp;
+
+    // Rounding in the accumulator never pays out more than was accrued
+    liquidity_pool.unclaimed_fees = liquidity_pool.unclaimed_fees.saturating_sub(amount);
+    liquidity_pool.last_updated = clock.unix_timestamp;
+
+    emit!(LpFeesClaimedEvent {
+        market: market_key,
+        liquidity_provider: ctx.accounts.liquidity_provider.key(),
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    assert_pool_vault_solvent(&ctx.accounts.market, &ctx.accounts.liquidity_pool, &mut ctx.accounts.liquidity_vault)?;
+
+    Ok(())
+}
+
+#[event]
+pub struct LpFeesClaimedEvent {
+    pub market: Pubkey,
+    pub liquidity_provider: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::*;
+use crate::errors::*;
+use crate::invariant::assert_pool_vault_solvent;
+use super::claim_position::pay_out_position;
+
+#[derive(Accounts)]
+pub struct ClaimOutcome<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.is_settled() @ PredictionMarketError::MarketNotResolved
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"amm_position", market.key().as_ref(), owner.key().as_ref(), &[position.outcome_id]],
+        bump = position.bump,
+        constraint = position.owner == owner.key() @ PredictionMarketError::NoPosition,
+        close = owner
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_pool", market.key().as_ref()],
+        bump = liquidity_pool.bump,
+        has_one = market,
+        has_one = liquidity_vault,
+        constraint = liquidity_pool.settled @ PredictionMarketError::PoolNotSettled
+    )]
+    pub liquidity_pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(mut)]
+    pub liquidity_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = owner_token_account.owner == owner.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<ClaimOutcome>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let position = &mut ctx.accounts.position;
+    let clock = Clock::get()?;
+
+    let market_key = market.key();
+    let seeds = &[
+        b"liquidity_pool",
+        market_key.as_ref(),
+        &[ctx.accounts.liquidity_pool.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let outcome = position.outcome_id;
+    let shares = position.shares;
+
+    // Every AMM share is backed by a complete set, so an Invalid or cancelled
+    // market pays the equal split like the pool's own reserves
+    let payout = pay_out_position(
+        market,
+        position,
+        None,
+        &ctx.accounts.liquidity_vault,
+        &ctx.accounts.owner_token_account,
+        ctx.accounts.liquidity_pool.to_account_info(),
+        signer_seeds,
+        &ctx.accounts.token_program,
+        clock.unix_timestamp,
+    )?;
+
+    // Claimed shares are no longer owed by the liquidity vault
+    let outstanding = ctx.accounts.liquidity_pool.shares_outstanding_mut(outcome);
+    *outstanding = outstanding
+        .checked_sub(shares)
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+
+    emit!(OutcomeClaimedEvent {
+        market: market.key(),
+        owner: ctx.accounts.owner.key(),
+        outcome,
+        amount: payout,
+        timestamp: clock.unix_timestamp,
+    });
+
+    assert_pool_vault_solvent(market, &ctx.accounts.liquidity_pool, &mut ctx.accounts.liquidity_vault)?;
+
+    Ok(())
+}
+
+#[event]
+pub struct OutcomeClaimedEvent {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub outcome: u8,
+    pub amount: u64,
+    pub timestamp: i64,
+}
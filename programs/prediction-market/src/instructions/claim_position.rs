@@ -0,0 +1,191 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::math::{apply_bps, mul_div_u64, Rounding};
+use crate::invariant::{assert_market_vault_solvent, market_liabilities};
+use super::close_position::assert_no_resting_orders;
+
+#[derive(Accounts)]
+pub struct ClaimPosition<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.is_settled() @ PredictionMarketError::MarketNotResolved
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), owner.key().as_ref(), &[position.outcome_id]],
+        bump = position.bump,
+        constraint = position.owner == owner.key() @ PredictionMarketError::NoPosition,
+        close = owner
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// CHECK: Order book PDA of the position's outcome, which may not exist yet;
+    /// deserialized in the handler when it does
+    #[account(
+        seeds = [b"order_book", market.key().as_ref(), &[position.outcome_id]],
+        bump
+    )]
+    pub order_book: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"outcome_tokens", market.key().as_ref()],
+        bump = outcome_tokens.bump,
+        has_one = market
+    )]
+    pub outcome_tokens: Box<Account<'info, OutcomeTokens>>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = owner_token_account.owner == owner.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<ClaimPosition>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let position = &mut ctx.accounts.position;
+    let clock = Clock::get()?;
+
+    // The position is closed with the claim, so orders that would settle
+    // into it must be cancelled first
+    assert_no_resting_orders(&ctx.accounts.order_book, ctx.accounts.owner.key())?;
+
+    // An Invalid or cancelled market refunds every outstanding share pro rata
+    // from the collateral left once parimutuel stakes and complete sets are covered
+    let liabilities = market_liabilities(market, Some(&ctx.accounts.outcome_tokens))?;
+    let available = ctx.accounts.market_vault.amount
+        .saturating_sub(liabilities.parimutuel)
+        .saturating_sub(liabilities.outcome_tokens);
+    let outstanding_shares = market.outcomes
+        .iter()
+        .try_fold(0u64, |total, o| total.checked_add(o.total_shares))
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    let seeds = &[
+        b"market",
+        market.market_id.as_bytes(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let shares = position.shares;
+    let payout = pay_out_position(
+        market,
+        position,
+        Some((available, outstanding_shares)),
+        &ctx.accounts.market_vault,
+        &ctx.accounts.owner_token_account,
+        market.to_account_info(),
+        signer_seeds,
+        &ctx.accounts.token_program,
+        clock.unix_timestamp,
+    )?;
+
+    // Claimed shares are retired so later refunds and the solvency check
+    // only count what is still outstanding
+    let outcome = &mut market.outcomes[position.outcome_id as usize];
+    outcome.total_shares = outcome.total_shares
+        .checked_sub(shares)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    market.total_claimed = market.total_claimed
+        .checked_add(payout)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    market.positions_paid = market.positions_paid
+        .checked_add(payout)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(PositionClaimedEvent {
+        market: market.key(),
+        owner: ctx.accounts.owner.key(),
+        outcome: position.outcome_id,
+        amount: payout,
+        timestamp: clock.unix_timestamp,
+    });
+
+    assert_market_vault_solvent(
+        &ctx.accounts.market,
+        Some(&ctx.accounts.outcome_tokens),
+        &mut ctx.accounts.market_vault,
+    )?;
+
+    Ok(())
+}
+
+/// Redeem every share of a resolved `position` from `vault` and book the
+/// payout against its cost basis as realized PnL. Each share pays its
+/// outcome's payout; on an Invalid or cancelled market, `refund_pool` of
+/// (available collateral, outstanding shares) refunds the position pro rata
+/// instead.
+///
+/// Returns the amount paid.
+#[allow(clippy::too_many_arguments)]
+pub fn pay_out_position<'info>(
+    market: &Market,
+    position: &mut Position,
+    refund_pool: Option<(u64, u64)>,
+    vault: &Account<'info, TokenAccount>,
+    recipient: &Account<'info, TokenAccount>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    token_program: &Program<'info, Token>,
+    timestamp: i64,
+) -> Result<u64> {
+    require!(position.shares > 0, PredictionMarketError::PositionAlreadyClaimed);
+
+    let payout = match refund_pool {
+        Some((available, outstanding_shares)) if market.is_refunding() => {
+            mul_div_u64(available, position.shares, outstanding_shares, Rounding::Down)?
+        }
+        _ => apply_bps(position.shares, market.payout_bps(position.outcome_id), Rounding::Down)?,
+    };
+    require!(payout > 0, PredictionMarketError::NoWinnings);
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        Transfer {
+            from: vault.to_account_info(),
+            to: recipient.to_account_info(),
+            authority,
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, payout)?;
+
+    let cost_basis = mul_div_u64(position.average_price, position.shares, 10000, Rounding::Down)?;
+    position.realized_pnl = position.realized_pnl
+        .checked_add(payout as i64 - cost_basis as i64)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    position.shares = 0;
+    position.last_updated = timestamp;
+
+    Ok(payout)
+}
+
+#[event]
+pub struct PositionClaimedEvent {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub outcome: u8,
+    pub amount: u64,
+    pub timestamp: i64,
+}
@@ -1,29 +1,39 @@
-```rust
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 use crate::state::*;
 use crate::errors::*;
+use crate::math::{apply_bps, Rounding};
+use crate::invariant::assert_market_vault_solvent;
 
 #[derive(Accounts)]
 pub struct ClaimWinnings<'info> {
     #[account(
         mut,
-        seeds = [b"market", market.creator.as_ref(), &market.market_id.to_le_bytes()],
+        seeds = [b"market", market.market_id.as_bytes()],
         bump = market.bump,
         constraint = market.status == MarketStatus::Resolved @ PredictionMarketError::MarketNotResolved,
     )]
-    pub market: Account<'info, Market>,
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        seeds = [b"market_resolution", market.key().as_ref()],
+        bump = market_resolution.bump,
+        constraint = market_resolution.resolved @ PredictionMarketError::MarketNotResolved,
+        constraint = Clock::get()?.unix_timestamp >= market_resolution.dispute_period_end @ PredictionMarketError::DisputePeriodActive,
+    )]
+    pub market_resolution: Box<Account<'info, MarketResolution>>,
 
     #[account(
         mut,
-        seeds = [b"position", market.key().as_ref(), user.key().as_ref()],
-        bump = position.bump,
-        constraint = position.market == market.key() @ PredictionMarketError::InvalidPosition,
-        constraint = position.user == user.key() @ PredictionMarketError::InvalidPosition,
-        constraint = !position.claimed @ PredictionMarketError::AlreadyClaimed,
+        seeds = [b"bet", market.key().as_ref(), user.key().as_ref()],
+        bump = bet.bump,
+        constraint = bet.market == market.key() @ PredictionMarketError::NoPosition,
+        constraint = bet.bettor == user.key() @ PredictionMarketError::NoPosition,
+        constraint = !bet.claimed @ PredictionMarketError::PositionAlreadyClaimed,
+        close = user,
     )]
-    pub position: Account<'info, Position>,
+    pub bet: Box<Account<'info, Bet>>,
 
     #[account(
         mut,
@@ -34,8 +44,8 @@ pub struct ClaimWinnings<'info> {
 
     #[account(
         mut,
-        constraint = user_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenAccount,
-        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccount,
+        constraint = user_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidAccountOwner,
     )]
     pub user_token_account: Account<'info, TokenAccount>,
 
@@ -46,35 +56,32 @@ pub struct ClaimWinnings<'info> {
     pub system_program: Program<'info, System>,
 }
 
-impl<'info> ClaimWinnings<'info> {
-    pub fn claim_winnings(&mut self) -> Result<()> {
-        let market = &mut self.market;
-        let position = &mut self.position;
+pub fn handler(ctx: Context<ClaimWinnings>) -> Result<()> {
+    // Calculate winnings based on the bet and market outcome
+    let (winning_outcome, winnings) = calculate_winnings(&ctx.accounts.market, &ctx.accounts.bet)?;
 
-        // Calculate winnings based on position and market outcome
-        let winnings = self.calculate_winnings()?;
+    let market = &mut ctx.accounts.market;
+    let bet = &mut ctx.accounts.bet;
 
-        require!(winnings > 0, PredictionMarketError::NoWinnings);
-
-        // Mark position as claimed
-        position.claimed = true;
-        position.claimed_at = Clock::get()?.unix_timestamp;
+    // Mark bet as claimed
+    bet.claimed = true;
 
+    // A losing bet pays nothing but is still closed to return its rent
+    if winnings > 0 {
         // Transfer winnings from market vault to user
-        let market_key = market.key();
         let seeds = &[
-            b"market_vault",
-            market_key.as_ref(),
-            &[market.vault_bump],
+            b"market",
+            market.market_id.as_bytes(),
+            &[market.bump],
         ];
         let signer_seeds = &[&seeds[..]];
 
         let transfer_ctx = CpiContext::new_with_signer(
-            self.token_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
             Transfer {
-                from: self.market_vault.to_account_info(),
-                to: self.user_token_account.to_account_info(),
-                authority: self.market_vault.to_account_info(),
+                from: ctx.accounts.market_vault.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: market.to_account_info(),
             },
             signer_seeds,
         );
@@ -83,82 +90,61 @@ impl<'info> ClaimWinnings<'info> {
 
         // Update market statistics
         market.total_claimed = market.total_claimed.checked_add(winnings)
-            .ok_or(PredictionMarketError::MathOverflow)?;
-
-        emit!(WinningsClaimedEvent {
-            market: market.key(),
-            user: self.user.key(),
-            position: position.key(),
-            amount: winnings,
-            outcome: position.outcome,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
-
-        Ok(())
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        market.bets_paid = market.bets_paid.checked_add(winnings)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
     }
 
-    fn calculate_winnings(&self) -> Result<u64> {
-        let market = &self.market;
-        let position = &self.position;
+    emit!(WinningsClaimedEvent {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        bet: bet.key(),
+        amount: winnings,
+        outcome: winning_outcome,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    assert_market_vault_solvent(&ctx.accounts.market, None, &mut ctx.accounts.market_vault)?;
 
-        // Check if user's position matches the winning outcome
-        let winning_outcome = match market.resolved_outcome {
+    Ok(())
+}
+
+fn calculate_winnings(market: &Market, bet: &Bet) -> Result<(u8, u64)> {
+    // An Invalid market refunds every stake at the same ratio
+    let (winning_outcome, winning_stake) = if market.resolved_invalid {
+        let total_stake: u64 = bet.outcomes.iter().map(|o| o.amount).sum();
+        (Market::INVALID_OUTCOME, total_stake)
+    } else {
+        let winning_outcome = match market.winning_outcome {
             Some(outcome) => outcome,
             None => return Err(PredictionMarketError::MarketNotResolved.into()),
         };
 
-        if position.outcome != winning_outcome {
-            return Ok(0); // No winnings for losing positions
-        }
+        // No winnings for bets that did not back the winning outcome
+        let winning_stake = bet.outcomes
+            .get(winning_outcome as usize)
+            .map(|o| o.amount)
+            .unwrap_or(0);
+        (winning_outcome, winning_stake)
+    };
 
-        // Calculate winnings based on position size and odds
-        let total_winning_pool = match winning_outcome {
-            Outcome::Yes => market.yes_pool,
-            Outcome::No => market.no_pool,
-        };
+    if winning_stake == 0 {
+        return Ok((winning_outcome, 0));
+    }
 
-        let total_losing_pool = match winning_outcome {
-            Outcome::Yes => market.no_pool,
-            Outcome::No => market.yes_pool,
-        };
+    // Winnings = winning_stake * payout_ratio, where payout_ratio is the
+    // net pool (after protocol fee) per unit of winning stake in basis points
+    let net_winnings = apply_bps(winning_stake, market.payout_ratio, Rounding::Down)?;
 
-        // Winnings = (position_size / total_winning_pool) * (total_losing_pool + total_winning_pool) - fees
-        let total_pool = total_winning_pool.checked_add(total_losing_pool)
-            .ok_or(PredictionMarketError::MathOverflow)?;
-
-        let gross_winnings = (position.amount as u128)
-            .checked_mul(total_pool as u128)
-            .ok_or(PredictionMarketError::MathOverflow)?
-            .checked_div(total_winning_pool as u128)
-            .ok_or(PredictionMarketError::MathOverflow)?;
-
-        // Apply platform fee (e.g., 2%)
-        let platform_fee = gross_winnings
-            .checked_mul(market.fee_rate as u128)
-            .ok_or(PredictionMarketError::MathOverflow)?
-            .checked_div(10000) // fee_rate is in basis points
-            .ok_or(PredictionMarketError::MathOverflow)?;
-
-        let net_winnings = gross_winnings
-            .checked_sub(platform_fee)
-            .ok_or(PredictionMarketError::MathOverflow)?;
-
-        // Ensure winnings don't exceed u64 max
-        if net_winnings > u64::MAX as u128 {
-            return Err(PredictionMarketError::MathOverflow.into());
-        }
-
-        Ok(net_winnings as u64)
-    }
+    Ok((winning_outcome, net_winnings))
 }
 
 #[event]
 pub struct WinningsClaimedEvent {
     pub market: Pubkey,
     pub user: Pubkey,
-    pub position: Pubkey,
+    pub bet: Pubkey,
     pub amount: u64,
-    pub outcome: Outcome,
+    pub outcome: u8,
     pub timestamp: i64,
 }
-```
\ No newline at end of file
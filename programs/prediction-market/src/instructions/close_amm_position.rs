@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use super::close_position::forfeit_position;
+
+#[derive(Accounts)]
+pub struct CloseAmmPosition<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"amm_position", market.key().as_ref(), owner.key().as_ref(), &[position.outcome_id]],
+        bump = position.bump,
+        constraint = position.owner == owner.key() @ PredictionMarketError::NoPosition,
+        close = owner
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// Omitted once the market is swept and the pool closed
+    #[account(
+        mut,
+        seeds = [b"liquidity_pool", market.key().as_ref()],
+        bump = liquidity_pool.bump,
+        has_one = market
+    )]
+    pub liquidity_pool: Option<Box<Account<'info, LiquidityPool>>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseAmmPosition>) -> Result<()> {
+    let position = &ctx.accounts.position;
+    forfeit_position(&ctx.accounts.market, position, ctx.accounts.owner.key())?;
+
+    // Forfeited losing shares are no longer held by a trader
+    match ctx.accounts.liquidity_pool.as_mut() {
+        Some(liquidity_pool) => {
+            let outstanding = liquidity_pool.shares_outstanding_mut(position.outcome_id);
+            *outstanding = outstanding
+                .checked_sub(position.shares)
+                .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+        }
+        None => require!(
+            ctx.accounts.market.status == MarketStatus::Swept,
+            PredictionMarketError::MarketNotSwept
+        ),
+    }
+
+    Ok(())
+}
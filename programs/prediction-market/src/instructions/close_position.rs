@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ClosePosition<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), owner.key().as_ref(), &[position.outcome_id]],
+        bump = position.bump,
+        constraint = position.owner == owner.key() @ PredictionMarketError::NoPosition,
+        close = owner
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// CHECK: Order book PDA of the position's outcome, which may not exist yet;
+    /// deserialized in the handler when it does
+    #[account(
+        seeds = [b"order_book", market.key().as_ref(), &[position.outcome_id]],
+        bump
+    )]
+    pub order_book: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ClosePosition>) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+    assert_no_resting_orders(&ctx.accounts.order_book, owner)?;
+    forfeit_position(&ctx.accounts.market, &ctx.accounts.position, owner)
+}
+
+/// Fail if `owner` has orders resting in `order_book`, which may not exist yet
+pub fn assert_no_resting_orders(order_book: &AccountInfo, owner: Pubkey) -> Result<()> {
+    // Resting asks hold shares taken out of the position and resting bids are
+    // filled into it, so both must be cancelled before it can be closed
+    if order_book.owner == &crate::ID {
+        let order_book = OrderBook::try_deserialize(&mut &order_book.try_borrow_data()?[..])?;
+        require!(
+            !order_book.bids.iter().chain(order_book.asks.iter()).any(|o| o.owner == owner),
+            PredictionMarketError::OpenOrdersRemaining
+        );
+    }
+    Ok(())
+}
+
+/// Check that `position` is empty or lost and emit its closing event; the
+/// caller closes the account
+pub fn forfeit_position(market: &Market, position: &Position, owner: Pubkey) -> Result<()> {
+    // Shares on an outcome that resolved with no payout are worthless, so a
+    // losing position can be closed without claiming; once the market is
+    // swept no shares can be claimed at all
+    let losing = market.status == MarketStatus::Resolved
+        && !market.resolved_invalid
+        && market.payout_bps(position.outcome_id) == 0;
+    require!(
+        position.shares == 0 || losing || market.status == MarketStatus::Swept,
+        PredictionMarketError::PositionNotSettled
+    );
+
+    let cost_basis = position.cost_basis(position.shares)?;
+    let realized_pnl = position.realized_pnl
+        .checked_sub(i64::try_from(cost_basis).map_err(|_| PredictionMarketError::ArithmeticOverflow)?)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(PositionClosedEvent {
+        market: position.market,
+        owner,
+        outcome: position.outcome_id,
+        shares_forfeited: position.shares,
+        realized_pnl,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PositionClosedEvent {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub outcome: u8,
+    pub shares_forfeited: u64,
+    pub realized_pnl: i64,
+    pub timestamp: i64,
+}
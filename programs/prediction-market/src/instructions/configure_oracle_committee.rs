@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ConfigureOracleCommittee<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.market_type == MarketType::Categorical @ PredictionMarketError::InvalidConfiguration,
+        constraint = market.price_feed.is_none() @ PredictionMarketError::InvalidConfiguration,
+        constraint = market.resolution_time > Clock::get()?.unix_timestamp @ PredictionMarketError::MarketExpired
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = OracleCommittee::space(),
+        seeds = [b"oracle_committee", market.key().as_ref()],
+        bump
+    )]
+    pub oracle_committee: Box<Account<'info, OracleCommittee>>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        seeds = [b"market_stats", market.key().as_ref()],
+        bump = market_stats.bump
+    )]
+    pub market_stats: Box<Account<'info, MarketStats>>,
+
+    #[account(
+        mut,
+        constraint = authority.key() == market.creator || authority.key() == global_state.authority
+            @ PredictionMarketError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ConfigureOracleCommittee<'info>>,
+    threshold: u8,
+    vote_deadline: i64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+
+    // Once the market has traded only the platform authority may change who resolves it
+    require!(
+        ctx.accounts.market_stats.total_trades == 0
+            || ctx.accounts.authority.key() == ctx.accounts.global_state.authority,
+        PredictionMarketError::ConfigurationLocked
+    );
+
+    // Committee members are passed as remaining accounts, one registered Oracle each
+    let mut members: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+    for account_info in ctx.remaining_accounts.iter() {
+        let oracle = Account::<Oracle>::try_from(account_info)?;
+        require!(
+            oracle.is_active && oracle.reputation_score >= ctx.accounts.global_state.min_oracle_reputation,
+            PredictionMarketError::UnauthorizedOracle
+        );
+        require!(
+            !members.contains(&oracle.key()),
+            PredictionMarketError::InvalidConfiguration
+        );
+        members.push(oracle.key());
+    }
+
+    // A strict majority threshold keeps two conflicting quorums impossible
+    require!(
+        !members.is_empty() && members.len() <= OracleCommittee::MAX_MEMBERS,
+        PredictionMarketError::InvalidConfiguration
+    );
+    require!(
+        threshold as usize <= members.len() && threshold as usize * 2 > members.len(),
+        PredictionMarketError::InvalidConfiguration
+    );
+    require!(
+        vote_deadline > market.resolution_time,
+        PredictionMarketError::InvalidTimeParameters
+    );
+
+    let oracle_committee = &mut ctx.accounts.oracle_committee;
+    oracle_committee.market = market.key();
+    oracle_committee.members = members;
+    oracle_committee.threshold = threshold;
+    oracle_committee.vote_deadline = vote_deadline;
+    oracle_committee.tallies = vec![0; market.outcomes.len()];
+    oracle_committee.total_votes = 0;
+    oracle_committee.bump = ctx.bumps.oracle_committee;
+
+    market.oracle_committee = Some(oracle_committee.key());
+
+    emit!(OracleCommitteeConfiguredEvent {
+        market: market.key(),
+        oracle_committee: oracle_committee.key(),
+        members: oracle_committee.members.len() as u8,
+        threshold,
+        vote_deadline,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct OracleCommitteeConfiguredEvent {
+    pub market: Pubkey,
+    pub oracle_committee: Pubkey,
+    pub members: u8,
+    pub threshold: u8,
+    pub vote_deadline: i64,
+    pub timestamp: i64,
+}
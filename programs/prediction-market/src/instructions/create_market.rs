@@ -1,21 +1,32 @@
-```rust
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount, Transfer};
 
 use crate::state::*;
 use crate::errors::*;
+use crate::lmsr;
+use crate::math::isqrt;
+use crate::utils::load_oracle_registry;
 
 #[derive(Accounts)]
-#[instruction(market_id: String, title: String, description: String, category: String, end_time: i64, resolution_source: String)]
+#[instruction(market_id: String)]
 pub struct CreateMarket<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.paused @ PredictionMarketError::MarketPaused
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
     #[account(
         init,
         payer = creator,
-        space = 8 + Market::INIT_SPACE,
+        space = Market::space(),
         seeds = [b"market", market_id.as_bytes()],
         bump
     )]
-    pub market: Account<'info, Market>,
+    pub market: Box<Account<'info, Market>>,
 
     #[account(
         init,
@@ -25,54 +36,74 @@ pub struct CreateMarket<'info> {
         seeds = [b"market_vault", market.key().as_ref()],
         bump
     )]
-    pub market_vault: Account<'info, TokenAccount>,
+    pub market_vault: Box<Account<'info, TokenAccount>>,
 
     #[account(
         init,
         payer = creator,
-        space = 8 + MarketStats::INIT_SPACE,
+        space = MarketStats::space(),
         seeds = [b"market_stats", market.key().as_ref()],
         bump
     )]
-    pub market_stats: Account<'info, MarketStats>,
+    pub market_stats: Box<Account<'info, MarketStats>>,
 
     #[account(
         init,
         payer = creator,
-        space = 8 + OutcomeTokens::INIT_SPACE,
+        space = OutcomeTokens::space(),
         seeds = [b"outcome_tokens", market.key().as_ref()],
         bump
     )]
-    pub outcome_tokens: Account<'info, OutcomeTokens>,
+    pub outcome_tokens: Box<Account<'info, OutcomeTokens>>,
 
     #[account(
         init,
         payer = creator,
-        token::mint = usdc_mint,
-        token::authority = outcome_tokens,
+        mint::decimals = usdc_mint.decimals,
+        mint::authority = outcome_tokens,
+        seeds = [b"outcome_a_mint", market.key().as_ref()],
+        bump
+    )]
+    pub outcome_a_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = creator,
+        mint::decimals = usdc_mint.decimals,
+        mint::authority = outcome_tokens,
+        seeds = [b"outcome_b_mint", market.key().as_ref()],
+        bump
+    )]
+    pub outcome_b_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = creator,
+        token::mint = outcome_a_mint,
+        token::authority = liquidity_pool,
         seeds = [b"yes_token_vault", market.key().as_ref()],
         bump
     )]
-    pub yes_token_vault: Account<'info, TokenAccount>,
+    pub yes_token_vault: Box<Account<'info, TokenAccount>>,
 
     #[account(
         init,
         payer = creator,
-        token::mint = usdc_mint,
-        token::authority = outcome_tokens,
+        token::mint = outcome_b_mint,
+        token::authority = liquidity_pool,
         seeds = [b"no_token_vault", market.key().as_ref()],
         bump
     )]
-    pub no_token_vault: Account<'info, TokenAccount>,
+    pub no_token_vault: Box<Account<'info, TokenAccount>>,
 
     #[account(
         init,
         payer = creator,
-        space = 8 + LiquidityPool::INIT_SPACE,
+        space = LiquidityPool::space(),
         seeds = [b"liquidity_pool", market.key().as_ref()],
         bump
     )]
-    pub liquidity_pool: Account<'info, LiquidityPool>,
+    pub liquidity_pool: Box<Account<'info, LiquidityPool>>,
 
     #[account(
         init,
@@ -82,16 +113,64 @@ pub struct CreateMarket<'info> {
         seeds = [b"liquidity_vault", market.key().as_ref()],
         bump
     )]
-    pub liquidity_vault: Account<'info, TokenAccount>,
+    pub liquidity_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = creator,
+        mint::decimals = usdc_mint.decimals,
+        mint::authority = liquidity_pool,
+        seeds = [b"lp_mint", market.key().as_ref()],
+        bump
+    )]
+    pub lp_token_mint: Box<Account<'info, Mint>>,
 
     #[account(
         init,
         payer = creator,
-        space = 8 + MarketResolution::INIT_SPACE,
+        space = LiquidityPosition::space(),
+        seeds = [b"liquidity_position", market.key().as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    pub creator_liquidity_position: Box<Account<'info, LiquidityPosition>>,
+
+    /// Receives the LP tokens backing the seeded reserves
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = lp_token_mint,
+        associated_token::authority = creator
+    )]
+    pub creator_lp_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = MarketResolution::space(),
         seeds = [b"market_resolution", market.key().as_ref()],
         bump
     )]
-    pub market_resolution: Account<'info, MarketResolution>,
+    pub market_resolution: Box<Account<'info, MarketResolution>>,
+
+    #[account(
+        init,
+        payer = creator,
+        token::mint = usdc_mint,
+        token::authority = protocol_fee_authority,
+        seeds = [b"protocol_fee", market.key().as_ref()],
+        bump
+    )]
+    pub protocol_fee_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: This is a PDA used as the protocol fee authority
+    #[account(
+        seeds = [b"protocol_fee_authority"],
+        bump
+    )]
+    pub protocol_fee_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Registry PDA of the market oracle, validated in the handler
+    pub oracle_registry: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub creator: Signer<'info>,
@@ -101,41 +180,105 @@ pub struct CreateMarket<'info> {
         constraint = creator_token_account.mint == usdc_mint.key(),
         constraint = creator_token_account.owner == creator.key()
     )]
-    pub creator_token_account: Account<'info, TokenAccount>,
+    pub creator_token_account: Box<Account<'info, TokenAccount>>,
 
-    pub usdc_mint: Account<'info, Mint>,
+    pub usdc_mint: Box<Account<'info, Mint>>,
 
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler(
     ctx: Context<CreateMarket>,
     market_id: String,
     title: String,
     description: String,
     category: String,
+    outcome_titles: Vec<String>,
     end_time: i64,
+    resolution_time: i64,
     resolution_source: String,
+    oracle: Pubkey,
+    min_bet_amount: u64,
+    max_bet_amount: u64,
     initial_liquidity: u64,
     creator_fee_bps: u16,
-    platform_fee_bps: u16,
+    cancellation_window: i64,
+    cancellation_fee_bps: u16,
+    cancellation_lock_time: i64,
+    lmsr_liquidity: u64,
+    market_type: MarketType,
+    lower_bound: i64,
+    upper_bound: i64,
+    price_feed: Option<PriceFeed>,
+    resolution_bond: u64,
+    dispute_window: i64,
 ) -> Result<()> {
     let clock = Clock::get()?;
     let current_time = clock.unix_timestamp;
 
     // Validate inputs
-    require!(market_id.len() <= 32, PredictionMarketError::MarketIdTooLong);
-    require!(title.len() <= 128, PredictionMarketError::TitleTooLong);
-    require!(description.len() <= 512, PredictionMarketError::DescriptionTooLong);
-    require!(category.len() <= 32, PredictionMarketError::CategoryTooLong);
-    require!(resolution_source.len() <= 128, PredictionMarketError::ResolutionSourceTooLong);
-    require!(end_time > current_time, PredictionMarketError::InvalidEndTime);
-    require!(end_time <= current_time + 365 * 24 * 60 * 60, PredictionMarketError::EndTimeTooFar);
-    require!(initial_liquidity >= 1000, PredictionMarketError::InsufficientInitialLiquidity);
-    require!(creator_fee_bps <= 1000, PredictionMarketError::CreatorFeeTooHigh);
-    require!(platform_fee_bps <= 500, PredictionMarketError::PlatformFeeTooHigh);
+    require!(market_id.len() <= Market::MAX_MARKET_ID_LENGTH, PredictionMarketError::InvalidMarketMetadata);
+    require!(title.len() <= Market::MAX_TITLE_LENGTH, PredictionMarketError::MarketTitleTooLong);
+    require!(description.len() <= Market::MAX_DESCRIPTION_LENGTH, PredictionMarketError::MarketDescriptionTooLong);
+    require!(category.len() <= Market::MAX_CATEGORY_LENGTH, PredictionMarketError::InvalidCategory);
+    require!(resolution_source.len() <= Market::MAX_RESOLUTION_SOURCE_LENGTH, PredictionMarketError::InvalidMarketMetadata);
+    require!(
+        outcome_titles.len() >= 2 && outcome_titles.len() <= Market::MAX_OUTCOMES,
+        PredictionMarketError::InvalidOutcomeCount
+    );
+    require!(
+        outcome_titles.iter().all(|t| t.len() <= Outcome::MAX_TITLE_LENGTH),
+        PredictionMarketError::OutcomeLabelTooLong
+    );
+    let global_state = &mut ctx.accounts.global_state;
+    let duration = end_time.checked_sub(current_time).ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    require!(duration >= global_state.min_market_duration, PredictionMarketError::InvalidMarketDuration);
+    require!(duration <= global_state.max_market_duration, PredictionMarketError::InvalidMarketDuration);
+    require!(resolution_time >= end_time, PredictionMarketError::InvalidTimeParameters);
+    if global_state.min_oracle_reputation > 0 {
+        let registry = load_oracle_registry(&ctx.accounts.oracle_registry, &oracle)?
+            .ok_or(PredictionMarketError::UnauthorizedOracle)?;
+        require!(
+            registry.is_active && registry.reputation_score >= global_state.min_oracle_reputation,
+            PredictionMarketError::UnauthorizedOracle
+        );
+    }
+    require!(min_bet_amount > 0, PredictionMarketError::InvalidBetAmount);
+    require!(max_bet_amount >= min_bet_amount, PredictionMarketError::InvalidBetAmount);
+    require!(
+        initial_liquidity / 2 > LiquidityPool::MINIMUM_LIQUIDITY,
+        PredictionMarketError::InsufficientInitialLiquidity
+    );
+    require!(creator_fee_bps <= GlobalState::MAX_CREATOR_FEE_BPS, PredictionMarketError::InvalidFeePercentage);
+    require!(cancellation_window >= 0, PredictionMarketError::InvalidTimeParameters);
+    require!(
+        cancellation_fee_bps <= Market::MAX_CANCELLATION_FEE_BPS,
+        PredictionMarketError::InvalidFeePercentage
+    );
+    require!(
+        cancellation_lock_time == 0 || cancellation_lock_time <= end_time,
+        PredictionMarketError::InvalidTimeParameters
+    );
+    if market_type == MarketType::Scalar {
+        require!(outcome_titles.len() == 2, PredictionMarketError::InvalidOutcomeCount);
+        require!(lower_bound < upper_bound, PredictionMarketError::InvalidMarketMetadata);
+    }
+    if let Some(feed) = &price_feed {
+        require!(outcome_titles.len() == 2, PredictionMarketError::InvalidOutcomeCount);
+        require!(
+            feed.comparison != PriceComparison::Between || feed.threshold <= feed.upper_threshold,
+            PredictionMarketError::InvalidPriceFeed
+        );
+        require!(feed.max_staleness >= 0, PredictionMarketError::InvalidPriceFeed);
+    }
+    require!(
+        dispute_window >= 0 && (resolution_bond == 0 || dispute_window > 0),
+        PredictionMarketError::InvalidTimeParameters
+    );
 
     let market = &mut ctx.accounts.market;
     let market_stats = &mut ctx.accounts.market_stats;
@@ -151,13 +294,67 @@ pub fn handler(
     market.creator = ctx.accounts.creator.key();
     market.created_at = current_time;
     market.end_time = end_time;
+    market.resolution_time = resolution_time;
+    market.claim_deadline = i64::MAX; // nothing can be swept until the market settles
+    market.sweep_destination = None;
     market.resolution_source = resolution_source.clone();
+    market.oracle = oracle;
     market.status = MarketStatus::Active;
-    market.usdc_mint = ctx.accounts.usdc_mint.key();
+    market.token_mint = ctx.accounts.usdc_mint.key();
     market.market_vault = ctx.accounts.market_vault.key();
+    market.outcome_a_mint = ctx.accounts.outcome_a_mint.key();
+    market.outcome_b_mint = ctx.accounts.outcome_b_mint.key();
+    market.min_bet_amount = min_bet_amount;
+    market.max_bet_amount = max_bet_amount;
+    market.total_liquidity = initial_liquidity;
     market.creator_fee_bps = creator_fee_bps;
-    market.platform_fee_bps = platform_fee_bps;
+    market.platform_fee_bps = global_state.platform_fee_rate;
+    market.oracle_fee_bps = global_state.oracle_fee_rate;
+    market.creator_fees_accrued = 0;
+    market.platform_fees_accrued = 0;
+    market.oracle_fees_accrued = 0;
+    market.cancellation_window = cancellation_window;
+    market.cancellation_fee_bps = cancellation_fee_bps;
+    market.cancellation_lock_time = cancellation_lock_time;
     market.bump = ctx.bumps.market;
+    market.vault_bump = ctx.bumps.market_vault;
+
+    let outcome_count = outcome_titles.len();
+    let initial_odds = (outcome_count as u64) * 10000;
+    let initial_price = 10000 / outcome_count as u64;
+    market.outcomes = outcome_titles
+        .into_iter()
+        .enumerate()
+        .map(|(i, title)| Outcome {
+            id: i as u8,
+            title,
+            total_amount: 0,
+            bet_count: 0,
+            current_odds: initial_odds,
+            total_shares: 0,
+            price: initial_price,
+            last_price: initial_price,
+            volume_24h: 0,
+        })
+        .collect();
+
+    // Creator funds the LMSR market maker's worst-case loss up front
+    let lmsr_funding = if lmsr_liquidity > 0 {
+        lmsr::max_loss(lmsr_liquidity, outcome_count)?
+    } else {
+        0
+    };
+    market.lmsr_b = lmsr_liquidity;
+    market.lmsr_funding = lmsr_funding;
+
+    // Scalar markets settle a LONG/SHORT pair between the bounds
+    market.market_type = market_type;
+    market.lower_bound = lower_bound;
+    market.upper_bound = upper_bound;
+    market.resolved_value = None;
+    market.resolved_invalid = false;
+    market.price_feed = price_feed;
+    market.oracle_committee = None;
 
     // Initialize market stats
     market_stats.market = market.key();
@@ -182,14 +379,53 @@ pub fn handler(
 
     // Initialize liquidity pool
     liquidity_pool.market = market.key();
+    liquidity_pool.liquidity_vault = ctx.accounts.liquidity_vault.key();
+    liquidity_pool.lp_token_mint = ctx.accounts.lp_token_mint.key();
+    liquidity_pool.outcome_a_vault = ctx.accounts.yes_token_vault.key();
+    liquidity_pool.outcome_b_vault = ctx.accounts.no_token_vault.key();
     liquidity_pool.total_liquidity = initial_liquidity;
+    liquidity_pool.available_liquidity = initial_liquidity;
     liquidity_pool.yes_reserves = initial_liquidity / 2;
     liquidity_pool.no_reserves = initial_liquidity / 2;
-    liquidity_pool.liquidity_vault = ctx.accounts.liquidity_vault.key();
-    liquidity_pool.k_constant = (initial_liquidity / 2) * (initial_liquidity / 2);
+    liquidity_pool.k_constant = (liquidity_pool.yes_reserves as u128) * (liquidity_pool.no_reserves as u128);
+    // LP tokens for the seeded reserves go to the creator, less the minimum
+    // liquidity, which is counted in the supply but never minted
+    let seed_lp_tokens = isqrt(liquidity_pool.k_constant) as u64;
+    let creator_lp_tokens = seed_lp_tokens
+        .checked_sub(LiquidityPool::MINIMUM_LIQUIDITY)
+        .ok_or(PredictionMarketError::InsufficientInitialLiquidity)?;
+    liquidity_pool.total_lp_tokens = seed_lp_tokens;
     liquidity_pool.fee_rate_bps = 30; // 0.3% trading fee
+    liquidity_pool.withdrawal_fee_bps = 30; // 0.3% early withdrawal fee
+    liquidity_pool.total_fees_collected = 0;
+    liquidity_pool.fee_per_share = 0;
+    liquidity_pool.unclaimed_fees = 0;
+    liquidity_pool.settled = false;
+    liquidity_pool.settlement_value = 0;
+    liquidity_pool.yes_shares_outstanding = 0;
+    liquidity_pool.no_shares_outstanding = 0;
+    liquidity_pool.active_providers = 1;
+    liquidity_pool.utilization_rate = 0;
+    liquidity_pool.apr = 0;
+    liquidity_pool.created_at = current_time;
+    liquidity_pool.last_updated = current_time;
     liquidity_pool.bump = ctx.bumps.liquidity_pool;
 
+    let creator_position = &mut ctx.accounts.creator_liquidity_position;
+    creator_position.owner = ctx.accounts.creator.key();
+    creator_position.market = market.key();
+    creator_position.pool = liquidity_pool.key();
+    creator_position.lp_tokens = creator_lp_tokens;
+    creator_position.deposited_amount = initial_liquidity;
+    creator_position.outcome_a_contributed = liquidity_pool.yes_reserves;
+    creator_position.outcome_b_contributed = liquidity_pool.no_reserves;
+    creator_position.earned_fees = 0;
+    creator_position.fee_debt = 0;
+    creator_position.is_active = true;
+    creator_position.created_at = current_time;
+    creator_position.last_updated = current_time;
+    creator_position.bump = ctx.bumps.creator_liquidity_position;
+
     // Initialize market resolution
     market_resolution.market = market.key();
     market_resolution.resolved = false;
@@ -198,8 +434,20 @@ pub fn handler(
     market_resolution.resolver = None;
     market_resolution.resolution_data = String::new();
     market_resolution.dispute_period_end = 0;
+    market_resolution.attestation_nonce = 0;
+    market_resolution.bond_amount = resolution_bond;
+    market_resolution.dispute_window = dispute_window;
+    market_resolution.proposer = None;
+    market_resolution.proposed_outcome = None;
+    market_resolution.proposed_at = 0;
+    market_resolution.disputer = None;
+    market_resolution.disputed_outcome = None;
     market_resolution.bump = ctx.bumps.market_resolution;
 
+    global_state.total_markets = global_state.total_markets
+        .checked_add(1)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
     // Transfer initial liquidity from creator
     let transfer_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
@@ -211,6 +459,36 @@ pub fn handler(
     );
     token::transfer(transfer_ctx, initial_liquidity)?;
 
+    let market_key = market.key();
+    let pool_seeds = &[
+        b"liquidity_pool",
+        market_key.as_ref(),
+        &[ctx.bumps.liquidity_pool],
+    ];
+    let pool_signer = &[&pool_seeds[..]];
+    let mint_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        MintTo {
+            mint: ctx.accounts.lp_token_mint.to_account_info(),
+            to: ctx.accounts.creator_lp_token_account.to_account_info(),
+            authority: ctx.accounts.liquidity_pool.to_account_info(),
+        },
+        pool_signer,
+    );
+    token::mint_to(mint_ctx, creator_lp_tokens)?;
+
+    if lmsr_funding > 0 {
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.creator_token_account.to_account_info(),
+                to: ctx.accounts.market_vault.to_account_info(),
+                authority: ctx.accounts.creator.to_account_info(),
+            },
+        );
+        token::transfer(transfer_ctx, lmsr_funding)?;
+    }
+
     // Emit market creation event
     emit!(MarketCreatedEvent {
         market: market.key(),
@@ -245,4 +523,3 @@ pub struct MarketCreatedEvent {
     pub category: String,
     pub created_at: i64,
 }
-```
\ No newline at end of file
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct DeactivateOracle<'info> {
+    #[account(
+        mut,
+        seeds = [b"oracle", oracle.authority.as_ref()],
+        bump = oracle.bump,
+        constraint = oracle.is_active @ PredictionMarketError::UnauthorizedOracle
+    )]
+    pub oracle: Account<'info, Oracle>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// The oracle itself or the platform authority
+    #[account(
+        constraint = authority.key() == oracle.authority
+            || authority.key() == global_state.authority @ PredictionMarketError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<DeactivateOracle>) -> Result<()> {
+    let oracle = &mut ctx.accounts.oracle;
+
+    oracle.is_active = false;
+
+    emit!(OracleDeactivatedEvent {
+        oracle: oracle.key(),
+        authority: oracle.authority,
+        deactivated_by: ctx.accounts.authority.key(),
+        reputation_score: oracle.reputation_score,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct OracleDeactivatedEvent {
+    pub oracle: Pubkey,
+    pub authority: Pubkey,
+    pub deactivated_by: Pubkey,
+    pub reputation_score: u32,
+    pub timestamp: i64,
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct DisputeResolution<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_resolution", market.key().as_ref()],
+        bump = market_resolution.bump,
+        constraint = market_resolution.proposer.is_some() @ PredictionMarketError::NoDisputeToResolve,
+        constraint = market_resolution.disputer.is_none() @ PredictionMarketError::DisputeAlreadySubmitted,
+        constraint = Clock::get()?.unix_timestamp < market_resolution.dispute_period_end @ PredictionMarketError::DisputePeriodExpired
+    )]
+    pub market_resolution: Box<Account<'info, MarketResolution>>,
+
+    #[account(
+        mut,
+        seeds = [b"resolution_bond", market.key().as_ref()],
+        bump
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = disputer_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = disputer_token_account.owner == disputer.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub disputer_token_account: Account<'info, TokenAccount>,
+
+    pub disputer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<DisputeResolution>, outcome: u8) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let market_resolution = &mut ctx.accounts.market_resolution;
+    let clock = Clock::get()?;
+
+    require!(outcome < market.outcomes.len() as u8, PredictionMarketError::InvalidOutcome);
+    require!(
+        market_resolution.proposed_outcome != Some(outcome),
+        PredictionMarketError::InvalidDisputeEvidence
+    );
+
+    // Post the counter-bond
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.disputer_token_account.to_account_info(),
+            to: ctx.accounts.bond_vault.to_account_info(),
+            authority: ctx.accounts.disputer.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, market_resolution.bond_amount)?;
+
+    market_resolution.disputer = Some(ctx.accounts.disputer.key());
+    market_resolution.disputed_outcome = Some(outcome);
+
+    emit!(ResolutionDisputedEvent {
+        market: market.key(),
+        disputer: ctx.accounts.disputer.key(),
+        proposed_outcome: market_resolution.proposed_outcome.unwrap_or_default(),
+        disputed_outcome: outcome,
+        bond_amount: market_resolution.bond_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ResolutionDisputedEvent {
+    pub market: Pubkey,
+    pub disputer: Pubkey,
+    pub proposed_outcome: u8,
+    pub disputed_outcome: u8,
+    pub bond_amount: u64,
+    pub timestamp: i64,
+}
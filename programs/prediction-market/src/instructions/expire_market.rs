@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::utils::{record_oracle_resolution, validate_resolution_time};
+
+#[derive(Accounts)]
+pub struct ExpireMarket<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        seeds = [b"market_resolution", market.key().as_ref()],
+        bump = market_resolution.bump,
+        constraint = !market_resolution.resolved @ PredictionMarketError::MarketAlreadyResolved
+    )]
+    pub market_resolution: Box<Account<'info, MarketResolution>>,
+
+    /// Required when a resolution proposal posted a bond
+    #[account(
+        mut,
+        seeds = [b"resolution_bond", market.key().as_ref()],
+        bump
+    )]
+    pub bond_vault: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Required when the proposal was disputed; the proposer who funded
+    /// the bond vault receives its rent when it is closed
+    #[account(
+        mut,
+        constraint = market_resolution.proposer == Some(proposer.key()) @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub proposer: Option<UncheckedAccount<'info>>,
+
+    /// Required when the proposal was disputed; receives the proposer's bond back
+    #[account(
+        mut,
+        constraint = proposer_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint
+    )]
+    pub proposer_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Required when the proposal was disputed; receives the counter-bond back
+    #[account(
+        mut,
+        constraint = disputer_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint
+    )]
+    pub disputer_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Registry PDA of the market oracle, penalised if the oracle is registered
+    #[account(mut)]
+    pub oracle_registry: UncheckedAccount<'info>,
+
+    pub caller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<ExpireMarket>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let market_resolution = &ctx.accounts.market_resolution;
+    let clock = Clock::get()?;
+
+    // validate_resolution_time is false both before the window opens and
+    // once it has closed
+    let within_window = validate_resolution_time(
+        clock.unix_timestamp,
+        market.resolution_time,
+        Market::RESOLUTION_WINDOW,
+    )?;
+    require!(
+        clock.unix_timestamp > market.resolution_time && !within_window,
+        PredictionMarketError::MarketNotExpired
+    );
+
+    // An undisputed proposal settles through finalize_resolution instead. A
+    // disputed one left unarbitrated past the window is the oracle's failure,
+    // not the proposer's: both bonds are refunded and the oracle is penalised.
+    let mut bonds_refunded = 0u64;
+    if let Some(proposer) = market_resolution.proposer {
+        let disputer = market_resolution.disputer.ok_or(PredictionMarketError::DisputePeriodActive)?;
+
+        let bond_vault = ctx.accounts.bond_vault.as_ref()
+            .ok_or(PredictionMarketError::AccountNotInitialized)?;
+
+        let seeds = &[
+            b"market",
+            market.market_id.as_bytes(),
+            &[market.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        // The proposer also takes anything else in the vault, which leaves it
+        // empty so it can be closed
+        let proposer_refund = bond_vault.amount
+            .checked_sub(market_resolution.bond_amount)
+            .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+        let refunds = [
+            (proposer, ctx.accounts.proposer_token_account.as_ref(), proposer_refund),
+            (disputer, ctx.accounts.disputer_token_account.as_ref(), market_resolution.bond_amount),
+        ];
+        for (owner, token_account, amount) in refunds {
+            let token_account = token_account.ok_or(PredictionMarketError::TokenAccountNotFound)?;
+            require!(
+                token_account.owner == owner,
+                PredictionMarketError::InvalidAccountOwner
+            );
+
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: bond_vault.to_account_info(),
+                    to: token_account.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer_seeds,
+            );
+            token::transfer(transfer_ctx, amount)?;
+            bonds_refunded = bonds_refunded
+                .checked_add(amount)
+                .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        }
+
+        let proposer_account = ctx.accounts.proposer.as_ref()
+            .ok_or(PredictionMarketError::AccountNotInitialized)?;
+        let close_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: bond_vault.to_account_info(),
+                destination: proposer_account.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::close_account(close_ctx)?;
+
+        record_oracle_resolution(&ctx.accounts.oracle_registry, &market.oracle, false)?;
+
+        emit!(ResolutionBondsRefundedEvent {
+            market: market.key(),
+            proposer,
+            disputer,
+            bond_amount: market_resolution.bond_amount,
+            oracle: market.oracle,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    // Bettors recover their stakes through refund_bet; positions, outcome
+    // tokens and LP shares are refunded at the Invalid split
+    market.status = MarketStatus::Cancelled;
+    market.open_claims(clock.unix_timestamp);
+
+    emit!(MarketExpiredEvent {
+        market: market.key(),
+        expired_by: ctx.accounts.caller.key(),
+        resolution_deadline: market.resolution_time.saturating_add(Market::RESOLUTION_WINDOW),
+        bonds_refunded,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MarketExpiredEvent {
+    pub market: Pubkey,
+    pub expired_by: Pubkey,
+    pub resolution_deadline: i64,
+    pub bonds_refunded: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ResolutionBondsRefundedEvent {
+    pub market: Pubkey,
+    pub proposer: Pubkey,
+    pub disputer: Pubkey,
+    pub bond_amount: u64,
+    pub oracle: Pubkey,
+    pub timestamp: i64,
+}
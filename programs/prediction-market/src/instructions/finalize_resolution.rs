@@ -0,0 +1,272 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::utils::{record_oracle_resolution, require_active_oracle};
+use super::resolve_market::{settle_categorical, settle_invalid, MarketInvalidatedEvent, MarketResolvedEvent};
+
+#[derive(Accounts)]
+pub struct FinalizeResolution<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_resolution", market.key().as_ref()],
+        bump = market_resolution.bump,
+        constraint = market_resolution.proposer.is_some() @ PredictionMarketError::NoDisputeToResolve
+    )]
+    pub market_resolution: Box<Account<'info, MarketResolution>>,
+
+    /// CHECK: Registry PDA of the market oracle, which must be active if registered
+    /// and is credited when it made the proposal
+    #[account(mut)]
+    pub oracle_registry: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"resolution_bond", market.key().as_ref()],
+        bump
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: The proposer who funded the bond vault; receives its rent when it is closed
+    #[account(
+        mut,
+        constraint = market_resolution.proposer == Some(proposer.key()) @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// Token account of whichever party the bonds are paid to
+    #[account(
+        mut,
+        constraint = bond_recipient_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint
+    )]
+    pub bond_recipient_token_account: Account<'info, TokenAccount>,
+
+    /// Required when the oracle rules for neither side; receives the counter-bond back
+    #[account(
+        mut,
+        constraint = disputer_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint
+    )]
+    pub disputer_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+        token::mint = market.token_mint,
+        token::authority = market
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_fee", market.key().as_ref()],
+        bump,
+        token::mint = market.token_mint,
+        token::authority = protocol_fee_authority
+    )]
+    pub protocol_fee_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA used as the protocol fee authority
+    #[account(
+        seeds = [b"protocol_fee_authority"],
+        bump
+    )]
+    pub protocol_fee_authority: UncheckedAccount<'info>,
+
+    /// Anyone for an undisputed proposal; the market oracle arbitrates disputes
+    pub finalizer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<FinalizeResolution>, outcome: u8) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let market_resolution = &mut ctx.accounts.market_resolution;
+    let clock = Clock::get()?;
+
+    let proposer = market_resolution.proposer.ok_or(PredictionMarketError::NoDisputeToResolve)?;
+    let proposed_outcome = market_resolution.proposed_outcome.ok_or(PredictionMarketError::NoDisputeToResolve)?;
+
+    // An undisputed proposal stands once the window closes; a disputed one is
+    // settled by the oracle and both bonds go to the side that was right. If
+    // the oracle rules for neither side, each party gets its own bond back.
+    let bond_amount = market_resolution.bond_amount;
+    let (final_outcome, bond_recipient, refunded_disputer) = match market_resolution.disputer {
+        None => {
+            require!(
+                clock.unix_timestamp >= market_resolution.dispute_period_end,
+                PredictionMarketError::DisputePeriodActive
+            );
+            (proposed_outcome, proposer, None)
+        }
+        Some(disputer) => {
+            require!(
+                ctx.accounts.finalizer.key() == market.oracle,
+                PredictionMarketError::UnauthorizedOracle
+            );
+            require_active_oracle(&ctx.accounts.oracle_registry, &market.oracle)?;
+            require!(
+                outcome < market.outcomes.len() as u8 || outcome == Market::INVALID_OUTCOME,
+                PredictionMarketError::InvalidOutcome
+            );
+
+            if outcome == proposed_outcome {
+                (outcome, proposer, None)
+            } else if market_resolution.disputed_outcome == Some(outcome) {
+                (outcome, disputer, None)
+            } else {
+                (outcome, proposer, Some(disputer))
+            }
+        }
+    };
+    require!(
+        ctx.accounts.bond_recipient_token_account.owner == bond_recipient,
+        PredictionMarketError::InvalidAccountOwner
+    );
+
+    // The recipient takes everything in the vault beyond a refunded
+    // counter-bond, which leaves it empty so it can be closed
+    let disputer_refund = if refunded_disputer.is_some() { bond_amount } else { 0 };
+    let bond_payout = ctx.accounts.bond_vault.amount
+        .checked_sub(disputer_refund)
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+
+    let seeds = &[
+        b"market",
+        market.market_id.as_bytes(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.bond_vault.to_account_info(),
+            to: ctx.accounts.bond_recipient_token_account.to_account_info(),
+            authority: market.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, bond_payout)?;
+
+    if let Some(disputer) = refunded_disputer {
+        let disputer_token_account = ctx.accounts.disputer_token_account.as_ref()
+            .ok_or(PredictionMarketError::TokenAccountNotFound)?;
+        require!(
+            disputer_token_account.owner == disputer,
+            PredictionMarketError::InvalidAccountOwner
+        );
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.bond_vault.to_account_info(),
+                to: disputer_token_account.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, disputer_refund)?;
+    }
+
+    // Both bonds are settled; the proposer funded the vault's rent
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.bond_vault.to_account_info(),
+            destination: ctx.accounts.proposer.to_account_info(),
+            authority: market.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::close_account(close_ctx)?;
+
+    if final_outcome == Market::INVALID_OUTCOME {
+        let (total_pool, protocol_fee) = settle_invalid(
+            market,
+            &ctx.accounts.vault,
+            &ctx.accounts.protocol_fee_account,
+            &ctx.accounts.token_program,
+            clock.unix_timestamp,
+        )?;
+
+        emit!(MarketInvalidatedEvent {
+            market: market.key(),
+            total_pool,
+            payout_ratio: market.payout_ratio,
+            protocol_fee,
+            resolution_timestamp: clock.unix_timestamp,
+        });
+    } else {
+        let (total_pool, winning_pool, protocol_fee) = settle_categorical(
+            market,
+            &ctx.accounts.vault,
+            &ctx.accounts.protocol_fee_account,
+            &ctx.accounts.token_program,
+            final_outcome,
+            clock.unix_timestamp,
+        )?;
+
+        emit!(MarketResolvedEvent {
+            market: market.key(),
+            winning_outcome: final_outcome,
+            total_pool,
+            winning_pool,
+            payout_ratio: market.payout_ratio,
+            protocol_fee,
+            resolution_timestamp: clock.unix_timestamp,
+        });
+    }
+
+    // Record the resolution; claims open immediately
+    market_resolution.resolved = true;
+    market_resolution.resolution_time = clock.unix_timestamp;
+    market_resolution.winning_outcome = market.winning_outcome;
+    market_resolution.resolver = Some(ctx.accounts.finalizer.key());
+    market_resolution.dispute_period_end = clock.unix_timestamp;
+    if final_outcome == Market::INVALID_OUTCOME {
+        market_resolution.resolution_data = "invalid".to_string();
+    }
+
+    // Only the market's own oracle is credited, so proposals on markets it
+    // does not serve cannot build reputation; an overturned proposal counts
+    // against its accuracy
+    if proposer == market.oracle {
+        record_oracle_resolution(
+            &ctx.accounts.oracle_registry,
+            &proposer,
+            final_outcome == proposed_outcome,
+        )?;
+    }
+
+    emit!(ResolutionFinalizedEvent {
+        market: market.key(),
+        winning_outcome: final_outcome,
+        disputed: market_resolution.disputer.is_some(),
+        bond_recipient,
+        bond_payout,
+        disputer_refund,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ResolutionFinalizedEvent {
+    pub market: Pubkey,
+    pub winning_outcome: u8,
+    pub disputed: bool,
+    pub bond_recipient: Pubkey,
+    pub bond_payout: u64,
+    pub disputer_refund: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(outcome: u8)]
+pub struct InitializeOrderBook<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = outcome < market.outcomes.len() as u8 @ PredictionMarketError::InvalidOutcome
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = OrderBook::space(),
+        seeds = [b"order_book", market.key().as_ref(), &[outcome]],
+        bump
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = token_mint,
+        token::authority = order_book,
+        seeds = [b"book_vault", order_book.key().as_ref()],
+        bump
+    )]
+    pub book_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        constraint = token_mint.key() == market.token_mint @ PredictionMarketError::InvalidTokenMint
+    )]
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(ctx: Context<InitializeOrderBook>, outcome: u8) -> Result<()> {
+    let order_book = &mut ctx.accounts.order_book;
+
+    order_book.market = ctx.accounts.market.key();
+    order_book.outcome_id = outcome;
+    order_book.book_vault = ctx.accounts.book_vault.key();
+    order_book.bids = Vec::new();
+    order_book.asks = Vec::new();
+    order_book.fills = Vec::new();
+    order_book.next_order_id = 0;
+    order_book.total_fills = 0;
+    order_book.bump = ctx.bumps.order_book;
+    order_book.vault_bump = ctx.bumps.book_vault;
+
+    emit!(OrderBookInitializedEvent {
+        market: order_book.market,
+        order_book: order_book.key(),
+        outcome,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct OrderBookInitializedEvent {
+    pub market: Pubkey,
+    pub order_book: Pubkey,
+    pub outcome: u8,
+    pub timestamp: i64,
+}
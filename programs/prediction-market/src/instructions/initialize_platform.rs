@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct InitializePlatform<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = GlobalState::space(),
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<InitializePlatform>,
+    platform_fee_rate: u16,
+    oracle_fee_rate: u16,
+    min_market_duration: i64,
+    max_market_duration: i64,
+) -> Result<()> {
+    require!(
+        platform_fee_rate <= GlobalState::MAX_PLATFORM_FEE_RATE,
+        PredictionMarketError::InvalidFeePercentage
+    );
+    require!(
+        oracle_fee_rate <= GlobalState::MAX_ORACLE_FEE_RATE,
+        PredictionMarketError::InvalidFeePercentage
+    );
+    require!(
+        min_market_duration > 0 && max_market_duration >= min_market_duration,
+        PredictionMarketError::InvalidMarketDuration
+    );
+
+    let global_state = &mut ctx.accounts.global_state;
+
+    global_state.authority = ctx.accounts.authority.key();
+    global_state.total_markets = 0;
+    global_state.total_volume = 0;
+    global_state.total_fees_collected = 0;
+    global_state.platform_fee_rate = platform_fee_rate;
+    global_state.oracle_fee_rate = oracle_fee_rate;
+    global_state.min_market_duration = min_market_duration;
+    global_state.max_market_duration = max_market_duration;
+    global_state.paused = false;
+    global_state.min_oracle_reputation = 0;
+    global_state.bump = ctx.bumps.global_state;
+
+    emit!(PlatformInitializedEvent {
+        authority: global_state.authority,
+        platform_fee_rate,
+        oracle_fee_rate,
+        min_market_duration,
+        max_market_duration,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PlatformInitializedEvent {
+    pub authority: Pubkey,
+    pub platform_fee_rate: u16,
+    pub oracle_fee_rate: u16,
+    pub min_market_duration: i64,
+    pub max_market_duration: i64,
+    pub timestamp: i64,
+}
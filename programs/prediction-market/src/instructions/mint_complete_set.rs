@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::invariant::assert_market_vault_solvent;
+
+#[derive(Accounts)]
+pub struct MintCompleteSet<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.paused @ PredictionMarketError::MarketPaused
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        has_one = outcome_a_mint,
+        has_one = outcome_b_mint,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = Clock::get()?.unix_timestamp < market.end_time @ PredictionMarketError::MarketExpired,
+        constraint = market.outcomes.len() == 2 @ PredictionMarketError::InvalidOutcomeCount
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"outcome_tokens", market.key().as_ref()],
+        bump = outcome_tokens.bump,
+        has_one = market
+    )]
+    pub outcome_tokens: Box<Account<'info, OutcomeTokens>>,
+
+    #[account(mut)]
+    pub outcome_a_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub outcome_b_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_outcome_a_account.mint == outcome_a_mint.key() @ PredictionMarketError::InvalidTokenMint
+    )]
+    pub user_outcome_a_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_outcome_b_account.mint == outcome_b_mint.key() @ PredictionMarketError::InvalidTokenMint
+    )]
+    pub user_outcome_b_account: Account<'info, TokenAccount>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<MintCompleteSet>, amount: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let outcome_tokens = &mut ctx.accounts.outcome_tokens;
+    let clock = Clock::get()?;
+
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+
+    // Lock the collateral backing the set in the market vault
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.market_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    // Mint one YES and one NO token per unit of collateral
+    let market_key = market.key();
+    let seeds = &[
+        b"outcome_tokens",
+        market_key.as_ref(),
+        &[outcome_tokens.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let mint_a_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        MintTo {
+            mint: ctx.accounts.outcome_a_mint.to_account_info(),
+            to: ctx.accounts.user_outcome_a_account.to_account_info(),
+            authority: outcome_tokens.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::mint_to(mint_a_ctx, amount)?;
+
+    let mint_b_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        MintTo {
+            mint: ctx.accounts.outcome_b_mint.to_account_info(),
+            to: ctx.accounts.user_outcome_b_account.to_account_info(),
+            authority: outcome_tokens.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::mint_to(mint_b_ctx, amount)?;
+
+    outcome_tokens.yes_token_supply = outcome_tokens.yes_token_supply
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    outcome_tokens.no_token_supply = outcome_tokens.no_token_supply
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(CompleteSetMintedEvent {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        amount,
+        yes_token_supply: outcome_tokens.yes_token_supply,
+        no_token_supply: outcome_tokens.no_token_supply,
+        timestamp: clock.unix_timestamp,
+    });
+
+    assert_market_vault_solvent(&ctx.accounts.market, Some(&ctx.accounts.outcome_tokens), &mut ctx.accounts.market_vault)?;
+
+    Ok(())
+}
+
+#[event]
+pub struct CompleteSetMintedEvent {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub yes_token_supply: u64,
+    pub no_token_supply: u64,
+    pub timestamp: i64,
+}
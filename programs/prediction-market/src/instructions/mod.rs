@@ -1,26 +1,206 @@
-```rust
-use anchor_lang::prelude::*;
-
+pub mod initialize_platform;
+pub mod update_platform;
 pub mod create_market;
+pub mod update_market;
 pub mod place_bet;
+pub mod cancel_bet;
+pub mod buy_shares;
+pub mod sell_shares;
+pub mod buy_outcome;
+pub mod sell_outcome;
 pub mod resolve_market;
+pub mod resolve_scalar_market;
+pub mod resolve_from_pyth;
+pub mod resolve_from_switchboard;
+pub mod propose_resolution;
+pub mod dispute_resolution;
+pub mod finalize_resolution;
+pub mod configure_oracle_committee;
+pub mod submit_oracle_vote;
+pub mod resolve_by_quorum;
+pub mod register_oracle;
+pub mod deactivate_oracle;
+pub mod settle_oracle_vote;
 pub mod claim_winnings;
-pub mod initialize_platform;
-pub mod update_market;
-pub mod cancel_bet;
+pub mod claim_position;
+pub mod close_position;
+pub mod claim_outcome;
+pub mod close_amm_position;
+pub mod mint_complete_set;
+pub mod redeem_complete_set;
+pub mod redeem_winning_tokens;
+pub mod initialize_order_book;
+pub mod place_limit_order;
+pub mod cancel_order;
+pub mod cancel_market;
+pub mod expire_market;
+pub mod sweep_market;
+pub mod sweep_order_book;
+pub mod refund_bet;
+pub mod withdraw_creator_fees;
+pub mod withdraw_platform_fees;
+pub mod withdraw_oracle_fees;
 pub mod add_liquidity;
+pub mod add_liquidity_collateral;
 pub mod remove_liquidity;
+pub mod claim_lp_fees;
+pub mod settle_pool;
+pub mod settle_liquidity;
+pub mod audit_market;
+
+pub use initialize_platform::InitializePlatform;
+pub use update_platform::UpdatePlatform;
+pub use create_market::CreateMarket;
+pub use update_market::UpdateMarket;
+pub use place_bet::PlaceBet;
+pub use cancel_bet::CancelBet;
+pub use buy_shares::BuyShares;
+pub use sell_shares::SellShares;
+pub use buy_outcome::BuyOutcome;
+pub use sell_outcome::SellOutcome;
+pub use resolve_market::ResolveMarket;
+pub use resolve_scalar_market::ResolveScalarMarket;
+pub use resolve_from_pyth::ResolveFromPyth;
+pub use resolve_from_switchboard::ResolveFromSwitchboard;
+pub use propose_resolution::ProposeResolution;
+pub use dispute_resolution::DisputeResolution;
+pub use finalize_resolution::FinalizeResolution;
+pub use configure_oracle_committee::ConfigureOracleCommittee;
+pub use submit_oracle_vote::SubmitOracleVote;
+pub use resolve_by_quorum::ResolveByQuorum;
+pub use register_oracle::RegisterOracle;
+pub use deactivate_oracle::DeactivateOracle;
+pub use settle_oracle_vote::SettleOracleVote;
+pub use claim_winnings::ClaimWinnings;
+pub use claim_position::ClaimPosition;
+pub use close_position::ClosePosition;
+pub use claim_outcome::ClaimOutcome;
+pub use close_amm_position::CloseAmmPosition;
+pub use mint_complete_set::MintCompleteSet;
+pub use redeem_complete_set::RedeemCompleteSet;
+pub use redeem_winning_tokens::RedeemWinningTokens;
+pub use initialize_order_book::InitializeOrderBook;
+pub use place_limit_order::PlaceLimitOrder;
+pub use cancel_order::CancelOrder;
+pub use cancel_market::CancelMarket;
+pub use expire_market::ExpireMarket;
+pub use sweep_market::SweepMarket;
+pub use sweep_order_book::SweepOrderBook;
+pub use refund_bet::RefundBet;
+pub use withdraw_creator_fees::WithdrawCreatorFees;
+pub use withdraw_platform_fees::WithdrawPlatformFees;
+pub use withdraw_oracle_fees::WithdrawOracleFees;
+pub use add_liquidity::AddLiquidity;
+pub use add_liquidity_collateral::AddLiquidityCollateral;
+pub use remove_liquidity::RemoveLiquidity;
+pub use claim_lp_fees::ClaimLpFees;
+pub use settle_pool::SettlePool;
+pub use settle_liquidity::SettleLiquidity;
+pub use audit_market::{AuditMarket, MarketAudit};
 
-pub use create_market::*;
-pub use place_bet::*;
-pub use resolve_market::*;
-pub use claim_winnings::*;
-pub use initialize_platform::*;
-pub use update_market::*;
-pub use cancel_bet::*;
-pub use add_liquidity::*;
-pub use remove_liquidity::*;
+// Client account modules generated by `#[derive(Accounts)]`, which
+// `#[program]` resolves from the crate root
+pub(crate) use self::{
+    initialize_platform::__client_accounts_initialize_platform,
+    update_platform::__client_accounts_update_platform,
+    create_market::__client_accounts_create_market,
+    update_market::__client_accounts_update_market,
+    place_bet::__client_accounts_place_bet,
+    cancel_bet::__client_accounts_cancel_bet,
+    buy_shares::__client_accounts_buy_shares,
+    sell_shares::__client_accounts_sell_shares,
+    buy_outcome::__client_accounts_buy_outcome,
+    sell_outcome::__client_accounts_sell_outcome,
+    resolve_market::__client_accounts_resolve_market,
+    resolve_scalar_market::__client_accounts_resolve_scalar_market,
+    resolve_from_pyth::__client_accounts_resolve_from_pyth,
+    resolve_from_switchboard::__client_accounts_resolve_from_switchboard,
+    propose_resolution::__client_accounts_propose_resolution,
+    dispute_resolution::__client_accounts_dispute_resolution,
+    finalize_resolution::__client_accounts_finalize_resolution,
+    configure_oracle_committee::__client_accounts_configure_oracle_committee,
+    submit_oracle_vote::__client_accounts_submit_oracle_vote,
+    resolve_by_quorum::__client_accounts_resolve_by_quorum,
+    register_oracle::__client_accounts_register_oracle,
+    deactivate_oracle::__client_accounts_deactivate_oracle,
+    settle_oracle_vote::__client_accounts_settle_oracle_vote,
+    claim_winnings::__client_accounts_claim_winnings,
+    claim_position::__client_accounts_claim_position,
+    close_position::__client_accounts_close_position,
+    claim_outcome::__client_accounts_claim_outcome,
+    close_amm_position::__client_accounts_close_amm_position,
+    mint_complete_set::__client_accounts_mint_complete_set,
+    redeem_complete_set::__client_accounts_redeem_complete_set,
+    redeem_winning_tokens::__client_accounts_redeem_winning_tokens,
+    initialize_order_book::__client_accounts_initialize_order_book,
+    place_limit_order::__client_accounts_place_limit_order,
+    cancel_order::__client_accounts_cancel_order,
+    cancel_market::__client_accounts_cancel_market,
+    expire_market::__client_accounts_expire_market,
+    sweep_market::__client_accounts_sweep_market,
+    sweep_order_book::__client_accounts_sweep_order_book,
+    refund_bet::__client_accounts_refund_bet,
+    withdraw_creator_fees::__client_accounts_withdraw_creator_fees,
+    withdraw_platform_fees::__client_accounts_withdraw_platform_fees,
+    withdraw_oracle_fees::__client_accounts_withdraw_oracle_fees,
+    add_liquidity::__client_accounts_add_liquidity,
+    add_liquidity_collateral::__client_accounts_add_liquidity_collateral,
+    remove_liquidity::__client_accounts_remove_liquidity,
+    claim_lp_fees::__client_accounts_claim_lp_fees,
+    settle_pool::__client_accounts_settle_pool,
+    settle_liquidity::__client_accounts_settle_liquidity,
+    audit_market::__client_accounts_audit_market,
+};
 
-#[derive(Accounts)]
-pub struct Initialize {}
-```
\ No newline at end of file
+#[cfg(feature = "cpi")]
+pub(crate) use self::{
+    initialize_platform::__cpi_client_accounts_initialize_platform,
+    update_platform::__cpi_client_accounts_update_platform,
+    create_market::__cpi_client_accounts_create_market,
+    update_market::__cpi_client_accounts_update_market,
+    place_bet::__cpi_client_accounts_place_bet,
+    cancel_bet::__cpi_client_accounts_cancel_bet,
+    buy_shares::__cpi_client_accounts_buy_shares,
+    sell_shares::__cpi_client_accounts_sell_shares,
+    buy_outcome::__cpi_client_accounts_buy_outcome,
+    sell_outcome::__cpi_client_accounts_sell_outcome,
+    resolve_market::__cpi_client_accounts_resolve_market,
+    resolve_scalar_market::__cpi_client_accounts_resolve_scalar_market,
+    resolve_from_pyth::__cpi_client_accounts_resolve_from_pyth,
+    resolve_from_switchboard::__cpi_client_accounts_resolve_from_switchboard,
+    propose_resolution::__cpi_client_accounts_propose_resolution,
+    dispute_resolution::__cpi_client_accounts_dispute_resolution,
+    finalize_resolution::__cpi_client_accounts_finalize_resolution,
+    configure_oracle_committee::__cpi_client_accounts_configure_oracle_committee,
+    submit_oracle_vote::__cpi_client_accounts_submit_oracle_vote,
+    resolve_by_quorum::__cpi_client_accounts_resolve_by_quorum,
+    register_oracle::__cpi_client_accounts_register_oracle,
+    deactivate_oracle::__cpi_client_accounts_deactivate_oracle,
+    settle_oracle_vote::__cpi_client_accounts_settle_oracle_vote,
+    claim_winnings::__cpi_client_accounts_claim_winnings,
+    claim_position::__cpi_client_accounts_claim_position,
+    close_position::__cpi_client_accounts_close_position,
+    claim_outcome::__cpi_client_accounts_claim_outcome,
+    close_amm_position::__cpi_client_accounts_close_amm_position,
+    mint_complete_set::__cpi_client_accounts_mint_complete_set,
+    redeem_complete_set::__cpi_client_accounts_redeem_complete_set,
+    redeem_winning_tokens::__cpi_client_accounts_redeem_winning_tokens,
+    initialize_order_book::__cpi_client_accounts_initialize_order_book,
+    place_limit_order::__cpi_client_accounts_place_limit_order,
+    cancel_order::__cpi_client_accounts_cancel_order,
+    cancel_market::__cpi_client_accounts_cancel_market,
+    expire_market::__cpi_client_accounts_expire_market,
+    sweep_market::__cpi_client_accounts_sweep_market,
+    sweep_order_book::__cpi_client_accounts_sweep_order_book,
+    refund_bet::__cpi_client_accounts_refund_bet,
+    withdraw_creator_fees::__cpi_client_accounts_withdraw_creator_fees,
+    withdraw_platform_fees::__cpi_client_accounts_withdraw_platform_fees,
+    withdraw_oracle_fees::__cpi_client_accounts_withdraw_oracle_fees,
+    add_liquidity::__cpi_client_accounts_add_liquidity,
+    add_liquidity_collateral::__cpi_client_accounts_add_liquidity_collateral,
+    remove_liquidity::__cpi_client_accounts_remove_liquidity,
+    claim_lp_fees::__cpi_client_accounts_claim_lp_fees,
+    settle_pool::__cpi_client_accounts_settle_pool,
+    settle_liquidity::__cpi_client_accounts_settle_liquidity,
+    audit_market::__cpi_client_accounts_audit_market,
+};
@@ -1,46 +1,73 @@
-```rust
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::state::*;
 use crate::errors::*;
+use crate::math::{mul_div_u64, Rounding};
+use crate::utils::{accrue_fees, calculate_trading_fees, recalculate_outcome_odds};
+use crate::invariant::{assert_fee_account_solvent, assert_market_vault_solvent};
 
 #[derive(Accounts)]
 #[instruction(outcome: u8, amount: u64)]
 pub struct PlaceBet<'info> {
     #[account(
         mut,
-        seeds = [b"market", market.creator.as_ref(), market.market_id.to_le_bytes().as_ref()],
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.paused @ PredictionMarketError::MarketPaused
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
         bump = market.bump,
         constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
         constraint = Clock::get()?.unix_timestamp < market.end_time @ PredictionMarketError::MarketExpired,
+        constraint = market.market_type == MarketType::Categorical @ PredictionMarketError::FeatureNotEnabled,
+        constraint = market.lmsr_b == 0 @ PredictionMarketError::FeatureNotEnabled,
         constraint = outcome < market.outcomes.len() as u8 @ PredictionMarketError::InvalidOutcome
     )]
-    pub market: Account<'info, Market>,
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_stats", market.key().as_ref()],
+        bump = market_stats.bump
+    )]
+    pub market_stats: Box<Account<'info, MarketStats>>,
 
     #[account(
         init_if_needed,
         payer = bettor,
-        space = 8 + Bet::INIT_SPACE,
+        space = Bet::space(),
         seeds = [b"bet", market.key().as_ref(), bettor.key().as_ref()],
         bump
     )]
-    pub bet: Account<'info, Bet>,
+    pub bet: Box<Account<'info, Bet>>,
 
     #[account(
         mut,
         constraint = bettor_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
-        constraint = bettor_token_account.owner == bettor.key() @ PredictionMarketError::InvalidTokenOwner
+        constraint = bettor_token_account.owner == bettor.key() @ PredictionMarketError::InvalidAccountOwner
     )]
     pub bettor_token_account: Account<'info, TokenAccount>,
 
     #[account(
         mut,
-        seeds = [b"vault", market.key().as_ref()],
+        seeds = [b"market_vault", market.key().as_ref()],
         bump = market.vault_bump,
         constraint = market_vault.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint
     )]
     pub market_vault: Account<'info, TokenAccount>,
 
+    #[account(
+        mut,
+        seeds = [b"protocol_fee", market.key().as_ref()],
+        bump,
+        token::mint = market.token_mint
+    )]
+    pub protocol_fee_account: Account<'info, TokenAccount>,
+
     #[account(mut)]
     pub bettor: Signer<'info>,
 
@@ -49,12 +76,13 @@ pub struct PlaceBet<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn place_bet(
+pub fn handler(
     ctx: Context<PlaceBet>,
     outcome: u8,
     amount: u64,
 ) -> Result<()> {
     let market = &mut ctx.accounts.market;
+    let market_stats = &mut ctx.accounts.market_stats;
     let bet = &mut ctx.accounts.bet;
     let bettor = &ctx.accounts.bettor;
     let clock = Clock::get()?;
@@ -62,14 +90,14 @@ pub fn place_bet(
     require!(amount > 0, PredictionMarketError::InvalidBetAmount);
     require!(
         amount >= market.min_bet_amount,
-        PredictionMarketError::BetAmountTooLow
+        PredictionMarketError::BetBelowMinimum
     );
     require!(
         amount <= market.max_bet_amount,
-        PredictionMarketError::BetAmountTooHigh
+        PredictionMarketError::BetAboveMaximum
     );
 
-    // Calculate current odds and validate liquidity
+    // Calculate current odds
     let total_pool = market.outcomes[outcome as usize].total_amount;
     let opposing_pool: u64 = market.outcomes
         .iter()
@@ -78,19 +106,11 @@ pub fn place_bet(
         .map(|(_, outcome_data)| outcome_data.total_amount)
         .sum();
 
-    // Ensure there's sufficient liquidity for potential payouts
-    let potential_payout = if opposing_pool > 0 {
-        amount.checked_mul(total_pool + opposing_pool)
-            .and_then(|x| x.checked_div(opposing_pool))
-            .ok_or(PredictionMarketError::MathOverflow)?
-    } else {
-        amount.checked_mul(2).ok_or(PredictionMarketError::MathOverflow)?
-    };
-
-    require!(
-        potential_payout <= market.max_payout_per_bet,
-        PredictionMarketError::PayoutTooHigh
-    );
+    // Creator and oracle fees come out of the stake; the platform's share is
+    // taken from the pool at resolution
+    let (creator_fee, _, oracle_fee) = calculate_trading_fees(market, amount)?;
+    let fee = accrue_fees(market, creator_fee, 0, oracle_fee)?;
+    let stake = amount.checked_sub(fee).ok_or(PredictionMarketError::ArithmeticOverflow)?;
 
     // Transfer tokens from bettor to market vault
     let transfer_ctx = CpiContext::new(
@@ -101,7 +121,19 @@ pub fn place_bet(
             authority: bettor.to_account_info(),
         },
     );
-    token::transfer(transfer_ctx, amount)?;
+    token::transfer(transfer_ctx, stake)?;
+
+    if fee > 0 {
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.bettor_token_account.to_account_info(),
+                to: ctx.accounts.protocol_fee_account.to_account_info(),
+                authority: bettor.to_account_info(),
+            },
+        );
+        token::transfer(transfer_ctx, fee)?;
+    }
 
     // Initialize or update bet account
     if bet.bettor == Pubkey::default() {
@@ -115,54 +147,98 @@ pub fn place_bet(
     // Update bet outcome
     bet.outcomes[outcome as usize].amount = bet.outcomes[outcome as usize]
         .amount
-        .checked_add(amount)
-        .ok_or(PredictionMarketError::MathOverflow)?;
+        .checked_add(stake)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
     
     bet.outcomes[outcome as usize].odds_at_bet = if opposing_pool > 0 {
-        ((total_pool + opposing_pool) as f64 / opposing_pool as f64 * 10000.0) as u64
+        mul_div_u64(total_pool + opposing_pool, 10000, opposing_pool, Rounding::Down)?
     } else {
         20000 // 2.0x odds when no opposing bets
     };
 
+    bet.outcomes[outcome as usize].bet_count = bet.outcomes[outcome as usize]
+        .bet_count
+        .checked_add(1)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
     bet.total_amount = bet.total_amount
-        .checked_add(amount)
-        .ok_or(PredictionMarketError::MathOverflow)?;
+        .checked_add(stake)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
     
     bet.last_bet_at = clock.unix_timestamp;
 
+    // Only stake placed inside the current window can be cancelled through it;
+    // later bets add to the window without extending it
+    let bet_outcome = &mut bet.outcomes[outcome as usize];
+    let window_end = bet_outcome.cancellable_since.saturating_add(market.cancellation_window);
+    if clock.unix_timestamp > window_end {
+        bet_outcome.cancellable_since = clock.unix_timestamp;
+        bet_outcome.cancellable_amount = 0;
+    }
+    bet_outcome.cancellable_amount = bet_outcome.cancellable_amount
+        .checked_add(stake)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
     // Update market outcome totals
     market.outcomes[outcome as usize].total_amount = market.outcomes[outcome as usize]
         .total_amount
-        .checked_add(amount)
-        .ok_or(PredictionMarketError::MathOverflow)?;
+        .checked_add(stake)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
     
     market.outcomes[outcome as usize].bet_count = market.outcomes[outcome as usize]
         .bet_count
         .checked_add(1)
-        .ok_or(PredictionMarketError::MathOverflow)?;
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
 
     // Update market totals
     market.total_volume = market.total_volume
         .checked_add(amount)
-        .ok_or(PredictionMarketError::MathOverflow)?;
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
     
     market.total_bets = market.total_bets
         .checked_add(1)
-        .ok_or(PredictionMarketError::MathOverflow)?;
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.total_volume = global_state.total_volume
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
 
     // Update market liquidity metrics
     market.last_bet_time = clock.unix_timestamp;
-    
-    // Calculate new odds for all outcomes
-    let total_market_pool: u64 = market.outcomes.iter().map(|o| o.total_amount).sum();
-    for (i, outcome_data) in market.outcomes.iter_mut().enumerate() {
-        if total_market_pool > outcome_data.total_amount {
-            outcome_data.current_odds = ((total_market_pool as f64 / outcome_data.total_amount as f64) * 10000.0) as u64;
-        } else {
-            outcome_data.current_odds = 10000; // 1.0x odds
+
+    // Update market stats
+    market_stats.total_volume = market_stats.total_volume
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    match outcome {
+        0 => {
+            market_stats.yes_volume = market_stats.yes_volume
+                .checked_add(amount)
+                .ok_or(PredictionMarketError::ArithmeticOverflow)?
+        }
+        1 => {
+            market_stats.no_volume = market_stats.no_volume
+                .checked_add(amount)
+                .ok_or(PredictionMarketError::ArithmeticOverflow)?
         }
+        _ => {}
     }
 
+    if bet.total_amount == stake {
+        market_stats.unique_traders = market_stats.unique_traders
+            .checked_add(1)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    }
+
+    market_stats.total_trades = market_stats.total_trades
+        .checked_add(1)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    
+    // Calculate new odds for all outcomes
+    recalculate_outcome_odds(&mut market.outcomes);
+
     // Emit bet placed event
     emit!(BetPlacedEvent {
         market: market.key(),
@@ -174,6 +250,9 @@ pub fn place_bet(
         total_market_volume: market.total_volume,
     });
 
+    assert_market_vault_solvent(&ctx.accounts.market, None, &mut ctx.accounts.market_vault)?;
+    assert_fee_account_solvent(&ctx.accounts.market, &mut ctx.accounts.protocol_fee_account)?;
+
     Ok(())
 }
 
@@ -186,5 +265,4 @@ pub struct BetPlacedEvent {
     pub odds: u64,
     pub timestamp: i64,
     pub total_market_volume: u64,
-}
-```
\ No newline at end of file
+}
\ No newline at end of file
@@ -0,0 +1,488 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::math::{apply_bps, Rounding};
+use crate::utils::{accrue_fees, calculate_trading_fees};
+
+#[derive(Accounts)]
+#[instruction(outcome: u8)]
+pub struct PlaceLimitOrder<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.paused @ PredictionMarketError::MarketPaused
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = Clock::get()?.unix_timestamp < market.end_time @ PredictionMarketError::MarketExpired
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_stats", market.key().as_ref()],
+        bump = market_stats.bump
+    )]
+    pub market_stats: Box<Account<'info, MarketStats>>,
+
+    #[account(
+        mut,
+        seeds = [b"order_book", market.key().as_ref(), &[outcome]],
+        bump = order_book.bump,
+        has_one = market,
+        has_one = book_vault
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+
+    #[account(mut)]
+    pub book_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = trader,
+        space = Position::space(),
+        seeds = [b"position", market.key().as_ref(), trader.key().as_ref(), &[outcome]],
+        bump
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(
+        mut,
+        constraint = trader_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = trader_token_account.owner == trader.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub trader_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_fee", market.key().as_ref()],
+        bump
+    )]
+    pub protocol_fee_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub trader: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Matches against resting orders first and rests any unfilled remainder.
+///
+/// Makers are settled in the same instruction: `remaining_accounts` must hold,
+/// in match order, each maker's collateral token account when buying or each
+/// maker's `Position` for this outcome when selling.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PlaceLimitOrder<'info>>,
+    outcome: u8,
+    side: TradeType,
+    price: u64,
+    quantity: u64,
+) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let market_stats = &mut ctx.accounts.market_stats;
+    let order_book = &mut ctx.accounts.order_book;
+    let position = &mut ctx.accounts.position;
+    let trader = ctx.accounts.trader.key();
+    let clock = Clock::get()?;
+
+    require!(quantity > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        (OrderBook::MIN_PRICE..=OrderBook::MAX_PRICE).contains(&price),
+        PredictionMarketError::InvalidOdds
+    );
+
+    // Initialize position on first order
+    if position.owner == Pubkey::default() {
+        position.owner = trader;
+        position.market = market.key();
+        position.outcome_id = outcome;
+        position.created_at = clock.unix_timestamp;
+        position.bump = ctx.bumps.position;
+    }
+
+    let mut fees = (0u64, 0u64, 0u64);
+    let mut maker_accounts = ctx.remaining_accounts.iter();
+    let mut remaining = quantity;
+    let mut total_fee = 0u64;
+    let mut seller_proceeds = 0u64;
+
+    match side {
+        TradeType::Buy => {
+            while remaining > 0 {
+                let best = match order_book.asks.first() {
+                    Some(best) if best.price <= price => best.clone(),
+                    _ => break,
+                };
+                require!(best.owner != trader, PredictionMarketError::WashTradingDetected);
+
+                let maker_info = maker_accounts.next().ok_or(PredictionMarketError::TokenAccountNotFound)?;
+                let maker_token_account = Account::<TokenAccount>::try_from(maker_info)?;
+                require!(
+                    maker_token_account.owner == best.owner,
+                    PredictionMarketError::InvalidAccountOwner
+                );
+                require!(
+                    maker_token_account.mint == market.token_mint,
+                    PredictionMarketError::InvalidTokenMint
+                );
+
+                let fill = remaining.min(best.quantity);
+                let cost = fill_cost(fill, best.price)?;
+                let fee = add_fill_fees(&mut fees, market, cost)?;
+
+                // Pay the maker at the resting ask price
+                let transfer_ctx = CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.trader_token_account.to_account_info(),
+                        to: maker_info.clone(),
+                        authority: ctx.accounts.trader.to_account_info(),
+                    },
+                );
+                token::transfer(transfer_ctx, cost)?;
+
+                if fill == best.quantity {
+                    order_book.asks.remove(0);
+                } else {
+                    order_book.asks[0].quantity -= fill;
+                }
+
+                position.add_shares(fill, cost)?;
+                record_fill(order_book, market_stats, market.key(), trader, outcome, TradeType::Buy, fill, best.price, cost, fee, clock.unix_timestamp)?;
+
+                emit!(OrderFilledEvent {
+                    market: market.key(),
+                    outcome,
+                    order_id: best.order_id,
+                    maker: best.owner,
+                    taker: trader,
+                    taker_side: TradeType::Buy,
+                    price: best.price,
+                    shares: fill,
+                    fee,
+                    timestamp: clock.unix_timestamp,
+                });
+
+                total_fee = total_fee
+                    .checked_add(fee)
+                    .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+                remaining -= fill;
+            }
+
+            if total_fee > 0 {
+                let transfer_ctx = CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.trader_token_account.to_account_info(),
+                        to: ctx.accounts.protocol_fee_account.to_account_info(),
+                        authority: ctx.accounts.trader.to_account_info(),
+                    },
+                );
+                token::transfer(transfer_ctx, total_fee)?;
+            }
+
+            // Escrow collateral for the resting bid
+            if remaining > 0 {
+                let collateral_locked = fill_cost(remaining, price)?;
+
+                let transfer_ctx = CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.trader_token_account.to_account_info(),
+                        to: ctx.accounts.book_vault.to_account_info(),
+                        authority: ctx.accounts.trader.to_account_info(),
+                    },
+                );
+                token::transfer(transfer_ctx, collateral_locked)?;
+
+                rest_order(order_book, TradeType::Buy, trader, price, remaining, collateral_locked, clock.unix_timestamp)?;
+            }
+        }
+        TradeType::Sell => {
+            require!(position.shares >= quantity, PredictionMarketError::InsufficientTokenBalance);
+
+            while remaining > 0 {
+                let best = match order_book.bids.first() {
+                    Some(best) if best.price >= price => best.clone(),
+                    _ => break,
+                };
+                require!(best.owner != trader, PredictionMarketError::WashTradingDetected);
+
+                let maker_info = maker_accounts.next().ok_or(PredictionMarketError::AccountNotInitialized)?;
+                // AMM positions share the layout, so only the LMSR position PDA is accepted
+                let (expected, _) = Pubkey::find_program_address(
+                    &[b"position", market.key().as_ref(), best.owner.as_ref(), &[outcome]],
+                    &crate::ID,
+                );
+                require_keys_eq!(maker_info.key(), expected, PredictionMarketError::InvalidAccountOwner);
+                let mut maker_position = Account::<Position>::try_from(maker_info)?;
+                require!(
+                    maker_position.owner == best.owner
+                        && maker_position.market == market.key()
+                        && maker_position.outcome_id == outcome,
+                    PredictionMarketError::InvalidAccountOwner
+                );
+
+                // The final fill releases whatever the bid still has escrowed
+                let fill = remaining.min(best.quantity);
+                let payment = if fill == best.quantity {
+                    best.collateral_locked
+                } else {
+                    fill_cost(fill, best.price)?.min(best.collateral_locked)
+                };
+                let fee = add_fill_fees(&mut fees, market, payment)?;
+
+                maker_position.add_shares(fill, payment)?;
+                maker_position.last_updated = clock.unix_timestamp;
+                maker_position.exit(&crate::ID)?;
+
+                if fill == best.quantity {
+                    order_book.bids.remove(0);
+                } else {
+                    order_book.bids[0].quantity -= fill;
+                    order_book.bids[0].collateral_locked -= payment;
+                }
+
+                remove_shares(position, fill, payment - fee)?;
+                record_fill(order_book, market_stats, market.key(), trader, outcome, TradeType::Sell, fill, best.price, payment, fee, clock.unix_timestamp)?;
+
+                emit!(OrderFilledEvent {
+                    market: market.key(),
+                    outcome,
+                    order_id: best.order_id,
+                    maker: best.owner,
+                    taker: trader,
+                    taker_side: TradeType::Sell,
+                    price: best.price,
+                    shares: fill,
+                    fee,
+                    timestamp: clock.unix_timestamp,
+                });
+
+                total_fee = total_fee
+                    .checked_add(fee)
+                    .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+                seller_proceeds = seller_proceeds
+                    .checked_add(payment - fee)
+                    .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+                remaining -= fill;
+            }
+
+            let market_key = market.key();
+            let seeds = &[
+                b"order_book",
+                market_key.as_ref(),
+                &[outcome],
+                &[order_book.bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            if seller_proceeds > 0 {
+                let transfer_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.book_vault.to_account_info(),
+                        to: ctx.accounts.trader_token_account.to_account_info(),
+                        authority: order_book.to_account_info(),
+                    },
+                    signer_seeds,
+                );
+                token::transfer(transfer_ctx, seller_proceeds)?;
+            }
+
+            if total_fee > 0 {
+                let transfer_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.book_vault.to_account_info(),
+                        to: ctx.accounts.protocol_fee_account.to_account_info(),
+                        authority: order_book.to_account_info(),
+                    },
+                    signer_seeds,
+                );
+                token::transfer(transfer_ctx, total_fee)?;
+            }
+
+            // Lock the unsold shares in the resting ask
+            if remaining > 0 {
+                position.shares = position.shares
+                    .checked_sub(remaining)
+                    .ok_or(PredictionMarketError::InsufficientTokenBalance)?;
+
+                rest_order(order_book, TradeType::Sell, trader, price, remaining, 0, clock.unix_timestamp)?;
+            }
+        }
+    }
+
+    position.last_updated = clock.unix_timestamp;
+    accrue_fees(&mut ctx.accounts.market, fees.0, fees.1, fees.2)?;
+
+    Ok(())
+}
+
+/// Taker fees on `amount` traded, added to the running (creator, platform,
+/// oracle) totals; returns the fee for this fill
+fn add_fill_fees(fees: &mut (u64, u64, u64), market: &Market, amount: u64) -> Result<u64> {
+    let (creator_fee, platform_fee, oracle_fee) = calculate_trading_fees(market, amount)?;
+
+    fees.0 = fees.0.checked_add(creator_fee).ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    fees.1 = fees.1.checked_add(platform_fee).ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    fees.2 = fees.2.checked_add(oracle_fee).ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    creator_fee
+        .checked_add(platform_fee)
+        .and_then(|v| v.checked_add(oracle_fee))
+        .ok_or(PredictionMarketError::ArithmeticOverflow.into())
+}
+
+/// Collateral for `shares` at `price` basis points, rounded up
+fn fill_cost(shares: u64, price: u64) -> Result<u64> {
+    apply_bps(shares, price, Rounding::Up)
+}
+
+fn remove_shares(position: &mut Position, shares: u64, proceeds: u64) -> Result<()> {
+    let cost_basis = position.cost_basis(shares)?;
+
+    position.realized_pnl = position.realized_pnl
+        .checked_add(proceeds as i64 - cost_basis as i64)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    position.shares = position.shares
+        .checked_sub(shares)
+        .ok_or(PredictionMarketError::InsufficientTokenBalance)?;
+
+    Ok(())
+}
+
+fn rest_order(
+    order_book: &mut OrderBook,
+    side: TradeType,
+    owner: Pubkey,
+    price: u64,
+    quantity: u64,
+    collateral_locked: u64,
+    timestamp: i64,
+) -> Result<()> {
+    let order = Order {
+        order_id: order_book.next_order_id,
+        owner,
+        price,
+        quantity,
+        collateral_locked,
+        created_at: timestamp,
+    };
+
+    // Insert behind every order at the same price to keep time priority
+    let orders = match side {
+        TradeType::Buy => &mut order_book.bids,
+        TradeType::Sell => &mut order_book.asks,
+    };
+    require!(orders.len() < OrderBook::MAX_ORDERS_PER_SIDE, PredictionMarketError::OrderBookFull);
+
+    let index = orders
+        .iter()
+        .position(|o| match side {
+            TradeType::Buy => o.price < price,
+            TradeType::Sell => o.price > price,
+        })
+        .unwrap_or(orders.len());
+    orders.insert(index, order);
+
+    emit!(OrderPlacedEvent {
+        market: order_book.market,
+        outcome: order_book.outcome_id,
+        order_id: order_book.next_order_id,
+        owner,
+        side,
+        price,
+        quantity,
+        timestamp,
+    });
+
+    order_book.next_order_id = order_book.next_order_id
+        .checked_add(1)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn record_fill(
+    order_book: &mut OrderBook,
+    market_stats: &mut MarketStats,
+    market: Pubkey,
+    trader: Pubkey,
+    outcome: u8,
+    trade_type: TradeType,
+    shares: u64,
+    price: u64,
+    total_cost: u64,
+    fee_paid: u64,
+    timestamp: i64,
+) -> Result<()> {
+    if order_book.fills.len() >= OrderBook::MAX_RECENT_FILLS {
+        order_book.fills.remove(0);
+    }
+    order_book.fills.push(Trade {
+        trader,
+        market,
+        outcome_id: outcome,
+        trade_type,
+        shares,
+        price,
+        total_cost,
+        fee_paid,
+        timestamp,
+        bump: order_book.bump,
+    });
+    order_book.total_fills = order_book.total_fills
+        .checked_add(1)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    market_stats.total_volume = market_stats.total_volume
+        .checked_add(total_cost)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    match outcome {
+        0 => market_stats.yes_volume = market_stats.yes_volume.saturating_add(total_cost),
+        1 => market_stats.no_volume = market_stats.no_volume.saturating_add(total_cost),
+        _ => {}
+    }
+    market_stats.total_trades = market_stats.total_trades
+        .checked_add(1)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    market_stats.last_trade_price = price;
+
+    Ok(())
+}
+
+#[event]
+pub struct OrderPlacedEvent {
+    pub market: Pubkey,
+    pub outcome: u8,
+    pub order_id: u64,
+    pub owner: Pubkey,
+    pub side: TradeType,
+    pub price: u64,
+    pub quantity: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OrderFilledEvent {
+    pub market: Pubkey,
+    pub outcome: u8,
+    pub order_id: u64,
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub taker_side: TradeType,
+    pub price: u64,
+    pub shares: u64,
+    pub fee: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ProposeResolution<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.market_type == MarketType::Categorical @ PredictionMarketError::InvalidResolutionData,
+        constraint = market.oracle_committee.is_none() @ PredictionMarketError::UnauthorizedOracle,
+        constraint = market.price_feed.is_none() @ PredictionMarketError::UnauthorizedOracle,
+        constraint = market.resolution_time <= Clock::get()?.unix_timestamp @ PredictionMarketError::MarketNotExpired
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_resolution", market.key().as_ref()],
+        bump = market_resolution.bump,
+        constraint = market_resolution.bond_amount > 0 @ PredictionMarketError::FeatureNotEnabled,
+        constraint = market_resolution.proposer.is_none() @ PredictionMarketError::DisputePeriodActive
+    )]
+    pub market_resolution: Box<Account<'info, MarketResolution>>,
+
+    #[account(
+        init,
+        payer = proposer,
+        token::mint = token_mint,
+        token::authority = market,
+        seeds = [b"resolution_bond", market.key().as_ref()],
+        bump
+    )]
+    pub bond_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        constraint = token_mint.key() == market.token_mint @ PredictionMarketError::InvalidTokenMint
+    )]
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = proposer_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = proposer_token_account.owner == proposer.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub proposer_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(ctx: Context<ProposeResolution>, outcome: u8) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let market_resolution = &mut ctx.accounts.market_resolution;
+    let clock = Clock::get()?;
+
+    require!(outcome < market.outcomes.len() as u8, PredictionMarketError::InvalidOutcome);
+
+    // Post the proposal bond
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.proposer_token_account.to_account_info(),
+            to: ctx.accounts.bond_vault.to_account_info(),
+            authority: ctx.accounts.proposer.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, market_resolution.bond_amount)?;
+
+    market_resolution.proposer = Some(ctx.accounts.proposer.key());
+    market_resolution.proposed_outcome = Some(outcome);
+    market_resolution.proposed_at = clock.unix_timestamp;
+    market_resolution.dispute_period_end = clock.unix_timestamp
+        .checked_add(market_resolution.dispute_window)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(ResolutionProposedEvent {
+        market: market.key(),
+        proposer: ctx.accounts.proposer.key(),
+        outcome,
+        bond_amount: market_resolution.bond_amount,
+        dispute_period_end: market_resolution.dispute_period_end,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ResolutionProposedEvent {
+    pub market: Pubkey,
+    pub proposer: Pubkey,
+    pub outcome: u8,
+    pub bond_amount: u64,
+    pub dispute_period_end: i64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::invariant::assert_market_vault_solvent;
+
+#[derive(Accounts)]
+pub struct RedeemCompleteSet<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        has_one = outcome_a_mint,
+        has_one = outcome_b_mint
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"outcome_tokens", market.key().as_ref()],
+        bump = outcome_tokens.bump,
+        has_one = market
+    )]
+    pub outcome_tokens: Box<Account<'info, OutcomeTokens>>,
+
+    #[account(mut)]
+    pub outcome_a_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub outcome_b_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_outcome_a_account.mint == outcome_a_mint.key() @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_outcome_a_account.owner == user.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub user_outcome_a_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_outcome_b_account.mint == outcome_b_mint.key() @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_outcome_b_account.owner == user.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub user_outcome_b_account: Account<'info, TokenAccount>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<RedeemCompleteSet>, amount: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let outcome_tokens = &mut ctx.accounts.outcome_tokens;
+    let clock = Clock::get()?;
+
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        ctx.accounts.user_outcome_a_account.amount >= amount
+            && ctx.accounts.user_outcome_b_account.amount >= amount,
+        PredictionMarketError::InsufficientTokenBalance
+    );
+
+    // Burn one YES and one NO token per unit of collateral returned
+    let burn_a_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.outcome_a_mint.to_account_info(),
+            from: ctx.accounts.user_outcome_a_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::burn(burn_a_ctx, amount)?;
+
+    let burn_b_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.outcome_b_mint.to_account_info(),
+            from: ctx.accounts.user_outcome_b_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::burn(burn_b_ctx, amount)?;
+
+    // Release the backing collateral
+    let seeds = &[
+        b"market",
+        market.market_id.as_bytes(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.market_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: market.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    outcome_tokens.yes_token_supply = outcome_tokens.yes_token_supply
+        .checked_sub(amount)
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+    outcome_tokens.no_token_supply = outcome_tokens.no_token_supply
+        .checked_sub(amount)
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+
+    emit!(CompleteSetRedeemedEvent {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        amount,
+        yes_token_supply: outcome_tokens.yes_token_supply,
+        no_token_supply: outcome_tokens.no_token_supply,
+        timestamp: clock.unix_timestamp,
+    });
+
+    assert_market_vault_solvent(&ctx.accounts.market, Some(&ctx.accounts.outcome_tokens), &mut ctx.accounts.market_vault)?;
+
+    Ok(())
+}
+
+#[event]
+pub struct CompleteSetRedeemedEvent {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub yes_token_supply: u64,
+    pub no_token_supply: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::math::{apply_bps, Rounding};
+use crate::invariant::assert_market_vault_solvent;
+
+#[derive(Accounts)]
+pub struct RedeemWinningTokens<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.is_settled() @ PredictionMarketError::MarketNotResolved
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"outcome_tokens", market.key().as_ref()],
+        bump = outcome_tokens.bump,
+        has_one = market
+    )]
+    pub outcome_tokens: Box<Account<'info, OutcomeTokens>>,
+
+    /// Mint of the outcome token being redeemed
+    #[account(
+        mut,
+        constraint = winning_mint.key() == market.outcome_a_mint || winning_mint.key() == market.outcome_b_mint
+            @ PredictionMarketError::InvalidTokenMint
+    )]
+    pub winning_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_winning_account.mint == winning_mint.key() @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_winning_account.owner == user.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub user_winning_account: Account<'info, TokenAccount>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<RedeemWinningTokens>, amount: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let outcome_tokens = &mut ctx.accounts.outcome_tokens;
+    let clock = Clock::get()?;
+
+    let winning_outcome = if ctx.accounts.winning_mint.key() == market.outcome_a_mint { 0 } else { 1 };
+    let payout_bps = market.payout_bps(winning_outcome);
+    require!(payout_bps > 0, PredictionMarketError::NoWinnings);
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        ctx.accounts.user_winning_account.amount >= amount,
+        PredictionMarketError::InsufficientTokenBalance
+    );
+
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.winning_mint.to_account_info(),
+            from: ctx.accounts.user_winning_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::burn(burn_ctx, amount)?;
+
+    // Each token redeems for its outcome's payout, one unit for a categorical winner
+    let payout = apply_bps(amount, payout_bps, Rounding::Down)?;
+
+    let seeds = &[
+        b"market",
+        market.market_id.as_bytes(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.market_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: market.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, payout)?;
+
+    if winning_outcome == 0 {
+        outcome_tokens.yes_token_supply = outcome_tokens.yes_token_supply
+            .checked_sub(amount)
+            .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+    } else {
+        outcome_tokens.no_token_supply = outcome_tokens.no_token_supply
+            .checked_sub(amount)
+            .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+    }
+
+    emit!(WinningTokensRedeemedEvent {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        outcome: winning_outcome,
+        amount,
+        payout,
+        timestamp: clock.unix_timestamp,
+    });
+
+    assert_market_vault_solvent(&ctx.accounts.market, Some(&ctx.accounts.outcome_tokens), &mut ctx.accounts.market_vault)?;
+
+    Ok(())
+}
+
+#[event]
+pub struct WinningTokensRedeemedEvent {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub outcome: u8,
+    pub amount: u64,
+    pub payout: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::invariant::assert_market_vault_solvent;
+
+#[derive(Accounts)]
+pub struct RefundBet<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Cancelled @ PredictionMarketError::MarketNotCancelled
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"bet", market.key().as_ref(), bettor.key().as_ref()],
+        bump = bet.bump,
+        constraint = bet.bettor == bettor.key() @ PredictionMarketError::NoPosition,
+        constraint = !bet.claimed @ PredictionMarketError::PositionAlreadyClaimed,
+        close = bettor
+    )]
+    pub bet: Box<Account<'info, Bet>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = bettor_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = bettor_token_account.owner == bettor.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub bettor_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<RefundBet>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let bet = &mut ctx.accounts.bet;
+
+    // Transfer bet amount back to bettor
+    let seeds = &[
+        b"market",
+        market.market_id.as_bytes(),
+        &[market.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.market_vault.to_account_info(),
+        to: ctx.accounts.bettor_token_account.to_account_info(),
+        authority: market.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token::transfer(cpi_ctx, bet.total_amount)?;
+
+    bet.claimed = true;
+    market.bets_paid = market.bets_paid
+        .checked_add(bet.total_amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(BetRefundedEvent {
+        market: market.key(),
+        bettor: ctx.accounts.bettor.key(),
+        amount: bet.total_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    assert_market_vault_solvent(&ctx.accounts.market, None, &mut ctx.accounts.market_vault)?;
+
+    Ok(())
+}
+
+#[event]
+pub struct BetRefundedEvent {
+    pub market: Pubkey,
+    pub bettor: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
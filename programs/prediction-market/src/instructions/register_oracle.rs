@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct RegisterOracle<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Oracle::space(),
+        seeds = [b"oracle", authority.key().as_ref()],
+        bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterOracle>, name: String, description: String) -> Result<()> {
+    require!(!name.is_empty() && name.len() <= Oracle::MAX_NAME_LENGTH, PredictionMarketError::InvalidConfiguration);
+    require!(description.len() <= Oracle::MAX_DESCRIPTION_LENGTH, PredictionMarketError::InvalidConfiguration);
+
+    let oracle = &mut ctx.accounts.oracle;
+    let clock = Clock::get()?;
+
+    oracle.authority = ctx.accounts.authority.key();
+    oracle.name = name;
+    oracle.description = description;
+    oracle.reputation_score = Oracle::reputation(0, 0);
+    oracle.total_resolutions = 0;
+    oracle.accurate_resolutions = 0;
+    oracle.created_at = clock.unix_timestamp;
+    oracle.is_active = true;
+    oracle.bump = ctx.bumps.oracle;
+
+    emit!(OracleRegisteredEvent {
+        oracle: oracle.key(),
+        authority: oracle.authority,
+        name: oracle.name.clone(),
+        reputation_score: oracle.reputation_score,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct OracleRegisteredEvent {
+    pub oracle: Pubkey,
+    pub authority: Pubkey,
+    pub name: String,
+    pub reputation_score: u32,
+    pub timestamp: i64,
+}
@@ -1,230 +1,206 @@
-```rust
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 use crate::state::*;
 use crate::errors::*;
+use crate::math::{apply_bps, mul_div_u64, Rounding};
+use crate::invariant::{assert_pool_vault_solvent, pool_liabilities};
 
 #[derive(Accounts)]
 pub struct RemoveLiquidity<'info> {
     #[account(
         mut,
-        seeds = [b"market", market.event_id.as_bytes()],
-        bump = market.bump,
-        has_one = authority
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump
     )]
-    pub market: Account<'info, Market>,
+    pub market: Box<Account<'info, Market>>,
 
     #[account(
         mut,
         seeds = [b"liquidity_pool", market.key().as_ref()],
-        bump = liquidity_pool.bump
+        bump = liquidity_pool.bump,
+        has_one = market,
+        has_one = liquidity_vault,
+        has_one = lp_token_mint
     )]
-    pub liquidity_pool: Account<'info, LiquidityPool>,
+    pub liquidity_pool: Box<Account<'info, LiquidityPool>>,
 
     #[account(
         mut,
         seeds = [b"liquidity_position", market.key().as_ref(), liquidity_provider.key().as_ref()],
         bump = liquidity_position.bump,
-        has_one = liquidity_provider,
+        constraint = liquidity_position.owner == liquidity_provider.key() @ PredictionMarketError::NoPosition,
         has_one = market
     )]
-    pub liquidity_position: Account<'info, LiquidityPosition>,
+    pub liquidity_position: Box<Account<'info, LiquidityPosition>>,
 
-    #[account(
-        mut,
-        associated_token::mint = market.base_mint,
-        associated_token::authority = liquidity_pool
-    )]
-    pub pool_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub liquidity_vault: Account<'info, TokenAccount>,
 
     #[account(
         mut,
-        associated_token::mint = market.base_mint,
-        associated_token::authority = liquidity_provider
+        constraint = provider_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = provider_token_account.owner == liquidity_provider.key() @ PredictionMarketError::InvalidAccountOwner
     )]
     pub provider_token_account: Account<'info, TokenAccount>,
 
     #[account(
         mut,
-        associated_token::mint = liquidity_pool.lp_mint,
-        associated_token::authority = liquidity_provider
+        constraint = provider_lp_token_account.mint == lp_token_mint.key() @ PredictionMarketError::InvalidTokenMint,
+        constraint = provider_lp_token_account.owner == liquidity_provider.key() @ PredictionMarketError::InvalidAccountOwner
     )]
     pub provider_lp_token_account: Account<'info, TokenAccount>,
 
-    #[account(
-        mut,
-        associated_token::mint = liquidity_pool.lp_mint,
-        associated_token::authority = liquidity_pool
-    )]
-    pub pool_lp_token_account: Account<'info, TokenAccount>,
-
-    /// CHECK: This is the mint authority for LP tokens
-    #[account(
-        seeds = [b"lp_mint_authority", liquidity_pool.key().as_ref()],
-        bump = liquidity_pool.lp_mint_authority_bump
-    )]
-    pub lp_mint_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub lp_token_mint: Account<'info, Mint>,
 
     pub liquidity_provider: Signer<'info>,
-    pub authority: Signer<'info>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
-impl<'info> RemoveLiquidity<'info> {
-    pub fn remove_liquidity(&mut self, lp_tokens_to_burn: u64) -> Result<()> {
-        let market = &mut self.market;
-        let liquidity_pool = &mut self.liquidity_pool;
-        let liquidity_position = &mut self.liquidity_position;
-
-        // Validate market state
-        require!(
-            market.status == MarketStatus::Active || market.status == MarketStatus::Resolved,
-            PredictionMarketError::MarketNotActive
-        );
-
-        // Validate LP token amount
-        require!(
-            lp_tokens_to_burn > 0,
-            PredictionMarketError::InvalidAmount
-        );
-
-        require!(
-            lp_tokens_to_burn <= liquidity_position.lp_tokens,
-            PredictionMarketError::InsufficientLpTokens
-        );
-
-        // Calculate withdrawal amounts based on pool share
-        let total_lp_supply = liquidity_pool.total_lp_tokens;
-        let pool_balance = self.pool_token_account.amount;
-        
-        require!(
-            total_lp_supply > 0,
-            PredictionMarketError::EmptyPool
-        );
-
-        // Calculate proportional withdrawal amount
-        let withdrawal_amount = (pool_balance as u128)
-            .checked_mul(lp_tokens_to_burn as u128)
-            .ok_or(PredictionMarketError::MathOverflow)?
-            .checked_div(total_lp_supply as u128)
-            .ok_or(PredictionMarketError::MathOverflow)? as u64;
-
-        require!(
-            withdrawal_amount > 0,
-            PredictionMarketError::InvalidAmount
-        );
-
-        require!(
-            withdrawal_amount <= pool_balance,
-            PredictionMarketError::InsufficientPoolBalance
-        );
-
-        // Calculate fees if market is still active
-        let (net_withdrawal, fee_amount) = if market.status == MarketStatus::Active {
-            let fee_amount = withdrawal_amount
-                .checked_mul(liquidity_pool.withdrawal_fee_bps as u64)
-                .ok_or(PredictionMarketError::MathOverflow)?
-                .checked_div(10000)
-                .ok_or(PredictionMarketError::MathOverflow)?;
-            
-            let net_withdrawal = withdrawal_amount
-                .checked_sub(fee_amount)
-                .ok_or(PredictionMarketError::MathOverflow)?;
-            
-            (net_withdrawal, fee_amount)
-        } else {
-            (withdrawal_amount, 0)
-        };
-
-        // Transfer tokens from pool to liquidity provider
-        let pool_seeds = &[
-            b"liquidity_pool",
-            market.key().as_ref(),
-            &[liquidity_pool.bump]
-        ];
-        let pool_signer = &[&pool_seeds[..]];
-
-        token::transfer(
-            CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                Transfer {
-                    from: self.pool_token_account.to_account_info(),
-                    to: self.provider_token_account.to_account_info(),
-                    authority: liquidity_pool.to_account_info(),
-                },
-                pool_signer,
-            ),
-            net_withdrawal,
-        )?;
-
-        // Burn LP tokens from provider
-        token::burn(
-            CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                token::Burn {
-                    mint: liquidity_pool.lp_mint.to_account_info(),
-                    from: self.provider_lp_token_account.to_account_info(),
-                    authority: self.liquidity_provider.to_account_info(),
-                },
-                &[],
-            ),
-            lp_tokens_to_burn,
-        )?;
-
-        // Update liquidity position
-        liquidity_position.lp_tokens = liquidity_position.lp_tokens
-            .checked_sub(lp_tokens_to_burn)
-            .ok_or(PredictionMarketError::MathOverflow)?;
-
-        liquidity_position.base_amount = liquidity_position.base_amount
-            .checked_sub(withdrawal_amount)
-            .ok_or(PredictionMarketError::MathOverflow)?;
-
-        liquidity_position.last_interaction = Clock::get()?.unix_timestamp;
-
-        // Update liquidity pool state
-        liquidity_pool.total_liquidity = liquidity_pool.total_liquidity
-            .checked_sub(withdrawal_amount)
-            .ok_or(PredictionMarketError::MathOverflow)?;
-
-        liquidity_pool.total_lp_tokens = liquidity_pool.total_lp_tokens
-            .checked_sub(lp_tokens_to_burn)
-            .ok_or(PredictionMarketError::MathOverflow)?;
-
-        if fee_amount > 0 {
-            liquidity_pool.total_fees_collected = liquidity_pool.total_fees_collected
-                .checked_add(fee_amount)
-                .ok_or(PredictionMarketError::MathOverflow)?;
-        }
-
-        // Update market liquidity
-        market.total_liquidity = market.total_liquidity
-            .checked_sub(withdrawal_amount)
-            .ok_or(PredictionMarketError::MathOverflow)?;
-
-        // Close position if no LP tokens remaining
-        if liquidity_position.lp_tokens == 0 {
-            liquidity_position.is_active = false;
-            liquidity_pool.active_providers = liquidity_pool.active_providers
-                .checked_sub(1)
-                .ok_or(PredictionMarketError::MathOverflow)?;
-        }
-
-        // Emit event
-        emit!(LiquidityRemovedEvent {
-            market: market.key(),
-            liquidity_provider: self.liquidity_provider.key(),
-            lp_tokens_burned: lp_tokens_to_burn,
-            base_amount_withdrawn: net_withdrawal,
-            fee_amount,
-            remaining_lp_tokens: liquidity_position.lp_tokens,
-            pool_total_liquidity: liquidity_pool.total_liquidity,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
-
-        Ok(())
+pub fn handler(ctx: Context<RemoveLiquidity>, lp_tokens_to_burn: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+    let liquidity_position = &mut ctx.accounts.liquidity_position;
+
+    // Validate market state; resolved pools pay out through settle_liquidity
+    require!(
+        market.status == MarketStatus::Active,
+        PredictionMarketError::MarketNotActive
+    );
+
+    // Validate LP token amount
+    require!(
+        lp_tokens_to_burn > 0,
+        PredictionMarketError::InvalidAmount
+    );
+
+    require!(
+        lp_tokens_to_burn <= liquidity_position.lp_tokens,
+        PredictionMarketError::InsufficientTokenBalance
+    );
+
+    // Calculate withdrawal amounts based on pool share; accrued LP fees
+    // are paid separately through claim_lp_fees
+    let total_lp_supply = liquidity_pool.total_lp_tokens;
+    // Fees and traders' AMM shares are owed from the vault before LPs
+    let pool_balance = ctx.accounts.liquidity_vault.amount
+        .checked_sub(pool_liabilities(market, liquidity_pool)?)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    
+    require!(
+        total_lp_supply > 0,
+        PredictionMarketError::NoLiquidity
+    );
+
+    // Calculate proportional withdrawal amount
+    let withdrawal_amount = mul_div_u64(pool_balance, lp_tokens_to_burn, total_lp_supply, Rounding::Down)?;
+
+    require!(
+        withdrawal_amount > 0,
+        PredictionMarketError::InvalidAmount
+    );
+
+    require!(
+        withdrawal_amount <= pool_balance,
+        PredictionMarketError::NoLiquidity
+    );
+
+    // Early withdrawal fee
+    let fee_amount = apply_bps(withdrawal_amount, liquidity_pool.withdrawal_fee_bps as u64, Rounding::Up)?;
+
+    let net_withdrawal = withdrawal_amount
+        .checked_sub(fee_amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    // Transfer tokens from pool to liquidity provider
+    let market_key = market.key();
+    let pool_seeds = &[
+        b"liquidity_pool",
+        market_key.as_ref(),
+        &[liquidity_pool.bump]
+    ];
+    let pool_signer = &[&pool_seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.liquidity_vault.to_account_info(),
+                to: ctx.accounts.provider_token_account.to_account_info(),
+                authority: liquidity_pool.to_account_info(),
+            },
+            pool_signer,
+        ),
+        net_withdrawal,
+    )?;
+
+    // Burn LP tokens from provider
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Burn {
+                mint: ctx.accounts.lp_token_mint.to_account_info(),
+                from: ctx.accounts.provider_lp_token_account.to_account_info(),
+                authority: ctx.accounts.liquidity_provider.to_account_info(),
+            },
+        ),
+        lp_tokens_to_burn,
+    )?;
+
+    // Update liquidity position, settling fees earned on the old balance
+    liquidity_position.settle_fees(liquidity_pool)?;
+    liquidity_position.lp_tokens = liquidity_position.lp_tokens
+        .checked_sub(lp_tokens_to_burn)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    liquidity_position.sync_fee_debt(liquidity_pool)?;
+
+    liquidity_position.deposited_amount = liquidity_position.deposited_amount
+        .saturating_sub(withdrawal_amount);
+
+    liquidity_position.last_updated = Clock::get()?.unix_timestamp;
+
+    // Update liquidity pool state
+    liquidity_pool.total_liquidity = liquidity_pool.total_liquidity
+        .saturating_sub(withdrawal_amount);
+
+    liquidity_pool.total_lp_tokens = liquidity_pool.total_lp_tokens
+        .checked_sub(lp_tokens_to_burn)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    if fee_amount > 0 {
+        liquidity_pool.total_fees_collected = liquidity_pool.total_fees_collected
+            .checked_add(fee_amount)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
     }
+
+    // Update market liquidity
+    market.total_liquidity = market.total_liquidity
+        .saturating_sub(withdrawal_amount);
+
+    // Close position if no LP tokens remaining
+    if liquidity_position.lp_tokens == 0 {
+        liquidity_position.is_active = false;
+        liquidity_pool.active_providers = liquidity_pool.active_providers
+            .checked_sub(1)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    }
+
+    // Emit event
+    emit!(LiquidityRemovedEvent {
+        market: market.key(),
+        liquidity_provider: ctx.accounts.liquidity_provider.key(),
+        lp_tokens_burned: lp_tokens_to_burn,
+        base_amount_withdrawn: net_withdrawal,
+        fee_amount,
+        remaining_lp_tokens: liquidity_position.lp_tokens,
+        pool_total_liquidity: liquidity_pool.total_liquidity,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    assert_pool_vault_solvent(&ctx.accounts.market, &ctx.accounts.liquidity_pool, &mut ctx.accounts.liquidity_vault)?;
+
+    Ok(())
 }
 
 #[event]
@@ -237,5 +213,4 @@ pub struct LiquidityRemovedEvent {
     pub remaining_lp_tokens: u64,
     pub pool_total_liquidity: u64,
     pub timestamp: i64,
-}
-```
\ No newline at end of file
+}
\ No newline at end of file
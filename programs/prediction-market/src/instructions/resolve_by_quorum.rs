@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::*;
+use crate::errors::*;
+use crate::utils::record_oracle_resolution;
+use super::resolve_market::{settle_categorical, MarketResolvedEvent};
+
+#[derive(Accounts)]
+pub struct ResolveByQuorum<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.resolution_time <= Clock::get()?.unix_timestamp @ PredictionMarketError::MarketNotExpired
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_resolution", market.key().as_ref()],
+        bump = market_resolution.bump,
+        constraint = market_resolution.proposer.is_none() @ PredictionMarketError::DisputePeriodActive
+    )]
+    pub market_resolution: Box<Account<'info, MarketResolution>>,
+
+    #[account(
+        seeds = [b"oracle_committee", market.key().as_ref()],
+        bump = oracle_committee.bump
+    )]
+    pub oracle_committee: Box<Account<'info, OracleCommittee>>,
+
+    /// CHECK: Registry PDA of the market oracle, credited with the resolution
+    #[account(mut)]
+    pub oracle_registry: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+        token::mint = market.token_mint,
+        token::authority = market
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_fee", market.key().as_ref()],
+        bump,
+        token::mint = market.token_mint,
+        token::authority = protocol_fee_authority
+    )]
+    pub protocol_fee_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA used as the protocol fee authority
+    #[account(
+        seeds = [b"protocol_fee_authority"],
+        bump
+    )]
+    pub protocol_fee_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<ResolveByQuorum>) -> Result<()> {
+    let oracle_committee = &ctx.accounts.oracle_committee;
+    let clock = Clock::get()?;
+
+    // Until the deadline a missing quorum may still form; after it the
+    // committee has failed to agree
+    let outcome = match oracle_committee.quorum_outcome() {
+        Some(outcome) => outcome,
+        None if clock.unix_timestamp >= oracle_committee.vote_deadline => {
+            return err!(PredictionMarketError::ConsensusNotReached);
+        }
+        None => return err!(PredictionMarketError::MultisigThresholdNotMet),
+    };
+
+    let market = &mut ctx.accounts.market;
+    let (total_pool, winning_pool, protocol_fee) = settle_categorical(
+        market,
+        &ctx.accounts.vault,
+        &ctx.accounts.protocol_fee_account,
+        &ctx.accounts.token_program,
+        outcome,
+        clock.unix_timestamp,
+    )?;
+
+    // Committee members are credited per vote through settle_oracle_vote
+    record_oracle_resolution(&ctx.accounts.oracle_registry, &market.oracle, true)?;
+
+    // Record the resolution
+    let market_resolution = &mut ctx.accounts.market_resolution;
+    market_resolution.resolved = true;
+    market_resolution.resolution_time = clock.unix_timestamp;
+    market_resolution.winning_outcome = Some(outcome);
+    market_resolution.resolver = Some(oracle_committee.key());
+    market_resolution.resolution_data = format!(
+        "quorum:{}/{}",
+        oracle_committee.tallies[outcome as usize],
+        oracle_committee.members.len()
+    );
+
+    emit!(MarketResolvedEvent {
+        market: market.key(),
+        winning_outcome: outcome,
+        total_pool,
+        winning_pool,
+        payout_ratio: market.payout_ratio,
+        protocol_fee,
+        resolution_timestamp: clock.unix_timestamp,
+    });
+
+    emit!(QuorumResolvedEvent {
+        market: market.key(),
+        oracle_committee: oracle_committee.key(),
+        winning_outcome: outcome,
+        votes_for_outcome: oracle_committee.tallies[outcome as usize],
+        total_votes: oracle_committee.total_votes,
+        threshold: oracle_committee.threshold,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct QuorumResolvedEvent {
+    pub market: Pubkey,
+    pub oracle_committee: Pubkey,
+    pub winning_outcome: u8,
+    pub votes_for_outcome: u8,
+    pub total_votes: u8,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
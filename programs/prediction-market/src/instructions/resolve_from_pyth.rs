@@ -0,0 +1,233 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::*;
+use crate::errors::*;
+use crate::pyth::{PriceUpdateV2, VerificationLevel};
+use crate::utils::{record_oracle_resolution, rescale_price};
+use super::resolve_market::{settle_categorical, MarketResolvedEvent};
+use super::resolve_scalar_market::settle_scalar;
+
+/// Default window after the resolution time in which a Pyth price must have
+/// been published to settle the market, in seconds
+pub const MAX_PYTH_PRICE_AGE: i64 = 60;
+
+/// Maximum Pyth confidence interval relative to price, in basis points
+pub const MAX_PYTH_CONFIDENCE_BPS: u64 = 200;
+
+#[derive(Accounts)]
+pub struct ResolveFromPyth<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.price_feed.is_some() @ PredictionMarketError::FeatureNotEnabled,
+        constraint = market.resolution_time <= Clock::get()?.unix_timestamp @ PredictionMarketError::MarketNotExpired
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_resolution", market.key().as_ref()],
+        bump = market_resolution.bump,
+        constraint = market_resolution.proposer.is_none() @ PredictionMarketError::DisputePeriodActive
+    )]
+    pub market_resolution: Box<Account<'info, MarketResolution>>,
+
+    pub price_update: Box<Account<'info, PriceUpdateV2>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+        token::mint = market.token_mint,
+        token::authority = market
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_fee", market.key().as_ref()],
+        bump,
+        token::mint = market.token_mint,
+        token::authority = protocol_fee_authority
+    )]
+    pub protocol_fee_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA used as the protocol fee authority
+    #[account(
+        seeds = [b"protocol_fee_authority"],
+        bump
+    )]
+    pub protocol_fee_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Registry PDA of the market oracle, credited when the oracle
+    /// cranks the resolution itself
+    #[account(mut)]
+    pub oracle_registry: UncheckedAccount<'info>,
+
+    /// Anyone may crank resolution once a fresh price is posted
+    pub resolver: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<ResolveFromPyth>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let price_update = &ctx.accounts.price_update;
+    let clock = Clock::get()?;
+
+    let feed = market.price_feed.clone().ok_or(PredictionMarketError::FeatureNotEnabled)?;
+    require!(feed.source == PriceFeedSource::Pyth, PredictionMarketError::InvalidPriceFeed);
+
+    // Only fully verified updates for the declared feed are accepted
+    require!(
+        price_update.price_message.feed_id == feed.feed_id,
+        PredictionMarketError::InvalidPriceFeed
+    );
+    require!(
+        price_update.verification_level == VerificationLevel::Full,
+        PredictionMarketError::InvalidPriceFeed
+    );
+
+    let max_staleness = if feed.max_staleness > 0 { feed.max_staleness } else { MAX_PYTH_PRICE_AGE };
+    // Settle on a price published right after the resolution time, not on
+    // whichever later price the cranker chooses to post
+    let price = price_update.get_price_published_after(
+        market.resolution_time,
+        max_staleness as u64,
+        &feed.feed_id,
+    )?;
+
+    // Reject prices whose confidence interval is too wide to settle on
+    require!(price.price != 0, PredictionMarketError::InvalidPriceFeed);
+    require!(
+        (price.conf as u128) * 10000 <= (price.price.unsigned_abs() as u128) * MAX_PYTH_CONFIDENCE_BPS as u128,
+        PredictionMarketError::InvalidPriceFeed
+    );
+
+    market.oracle_data = (price.price, price.conf, price.exponent, price.publish_time).try_to_vec()?;
+
+    let (winning_outcome, resolved_value) = settle_from_price(
+        market,
+        &mut ctx.accounts.market_resolution,
+        &ctx.accounts.vault,
+        &ctx.accounts.protocol_fee_account,
+        &ctx.accounts.token_program,
+        ctx.accounts.resolver.key(),
+        price.price,
+        price.exponent,
+        clock.unix_timestamp,
+    )?;
+
+    // Price feeds settle without the oracle; it only earns a resolution when
+    // it submits one itself
+    if ctx.accounts.resolver.key() == market.oracle {
+        record_oracle_resolution(&ctx.accounts.oracle_registry, &market.oracle, true)?;
+    }
+
+    emit!(PythResolvedEvent {
+        market: market.key(),
+        feed_id: feed.feed_id,
+        price: price.price,
+        conf: price.conf,
+        exponent: price.exponent,
+        publish_time: price.publish_time,
+        winning_outcome,
+        resolved_value,
+        resolution_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Resolve a price-feed market from an observed `price` at `exponent`.
+///
+/// Binary markets resolve YES (outcome 0) when the feed's comparison holds;
+/// scalar markets settle at the price rescaled to the feed's exponent.
+/// Returns the winning outcome or the resolved scalar value.
+#[allow(clippy::too_many_arguments)]
+pub fn settle_from_price<'info>(
+    market: &mut Account<'info, Market>,
+    market_resolution: &mut MarketResolution,
+    vault: &Account<'info, TokenAccount>,
+    protocol_fee_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    resolver: Pubkey,
+    price: i64,
+    exponent: i32,
+    timestamp: i64,
+) -> Result<(Option<u8>, Option<i64>)> {
+    let feed = market.price_feed.clone().ok_or(PredictionMarketError::FeatureNotEnabled)?;
+
+    let (winning_outcome, resolved_value) = match market.market_type {
+        MarketType::Scalar => {
+            let value = rescale_price(price, exponent, feed.exponent)?;
+            let value = i64::try_from(value).map_err(|_| PredictionMarketError::ArithmeticOverflow)?;
+            settle_scalar(market, value, timestamp);
+
+            (None, market.resolved_value)
+        }
+        MarketType::Categorical => {
+            // Compare at the finer of the two exponents so no precision is lost
+            let common_exponent = exponent.min(feed.exponent);
+            let observed = rescale_price(price, exponent, common_exponent)?;
+            let threshold = rescale_price(feed.threshold, feed.exponent, common_exponent)?;
+            let upper_threshold = rescale_price(feed.upper_threshold, feed.exponent, common_exponent)?;
+
+            let condition_met = match feed.comparison {
+                PriceComparison::Above => observed > threshold,
+                PriceComparison::Below => observed < threshold,
+                PriceComparison::Between => observed >= threshold && observed <= upper_threshold,
+            };
+            let outcome = if condition_met { 0 } else { 1 };
+
+            let (total_pool, winning_pool, protocol_fee) = settle_categorical(
+                market,
+                vault,
+                protocol_fee_account,
+                token_program,
+                outcome,
+                timestamp,
+            )?;
+
+            emit!(MarketResolvedEvent {
+                market: market.key(),
+                winning_outcome: outcome,
+                total_pool,
+                winning_pool,
+                payout_ratio: market.payout_ratio,
+                protocol_fee,
+                resolution_timestamp: timestamp,
+            });
+
+            (Some(outcome), None)
+        }
+    };
+
+    // Record the resolution
+    let source = match feed.source {
+        PriceFeedSource::Pyth => "pyth",
+        PriceFeedSource::Switchboard => "switchboard",
+    };
+    market_resolution.resolved = true;
+    market_resolution.resolution_time = timestamp;
+    market_resolution.winning_outcome = winning_outcome;
+    market_resolution.resolver = Some(resolver);
+    market_resolution.resolution_data = format!("{}:{}e{}", source, price, exponent);
+
+    Ok((winning_outcome, resolved_value))
+}
+
+#[event]
+pub struct PythResolvedEvent {
+    pub market: Pubkey,
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub winning_outcome: Option<u8>,
+    pub resolved_value: Option<i64>,
+    pub resolution_timestamp: i64,
+}
@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::*;
+use crate::errors::*;
+use crate::switchboard::AggregatorAccountData;
+use crate::utils::record_oracle_resolution;
+use super::resolve_from_pyth::settle_from_price;
+
+/// Default window after the resolution time in which a Switchboard round must
+/// have opened to settle the market, in seconds
+pub const MAX_SWITCHBOARD_STALENESS: i64 = 300;
+
+#[derive(Accounts)]
+pub struct ResolveFromSwitchboard<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.price_feed.is_some() @ PredictionMarketError::FeatureNotEnabled,
+        constraint = market.resolution_time <= Clock::get()?.unix_timestamp @ PredictionMarketError::MarketNotExpired
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_resolution", market.key().as_ref()],
+        bump = market_resolution.bump,
+        constraint = market_resolution.proposer.is_none() @ PredictionMarketError::DisputePeriodActive
+    )]
+    pub market_resolution: Box<Account<'info, MarketResolution>>,
+
+    pub aggregator: AccountLoader<'info, AggregatorAccountData>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+        token::mint = market.token_mint,
+        token::authority = market
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_fee", market.key().as_ref()],
+        bump,
+        token::mint = market.token_mint,
+        token::authority = protocol_fee_authority
+    )]
+    pub protocol_fee_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA used as the protocol fee authority
+    #[account(
+        seeds = [b"protocol_fee_authority"],
+        bump
+    )]
+    pub protocol_fee_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Registry PDA of the market oracle, credited when the oracle
+    /// cranks the resolution itself
+    #[account(mut)]
+    pub oracle_registry: UncheckedAccount<'info>,
+
+    /// Anyone may crank resolution while the aggregator's latest round falls in
+    /// the resolution window
+    pub resolver: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<ResolveFromSwitchboard>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    let feed = market.price_feed.clone().ok_or(PredictionMarketError::FeatureNotEnabled)?;
+    require!(feed.source == PriceFeedSource::Switchboard, PredictionMarketError::InvalidPriceFeed);
+    require!(
+        ctx.accounts.aggregator.key().to_bytes() == feed.feed_id,
+        PredictionMarketError::InvalidPriceFeed
+    );
+
+    let aggregator = ctx.accounts.aggregator.load()?;
+    let round = aggregator.latest_confirmed_round;
+
+    // The round must have opened right after resolution time and be backed
+    // by enough oracle responses
+    let max_staleness = if feed.max_staleness > 0 { feed.max_staleness } else { MAX_SWITCHBOARD_STALENESS };
+    aggregator.check_round_window(market.resolution_time, max_staleness)?;
+    require!(
+        round.num_success >= feed.min_oracle_responses.max(1),
+        PredictionMarketError::InvalidPriceFeed
+    );
+
+    let result = aggregator
+        .get_result()
+        .map_err(|_| PredictionMarketError::InvalidPriceFeed)?;
+
+    // Drop precision until the mantissa fits the i64 price representation
+    let mut mantissa = result.mantissa;
+    let mut scale = result.scale;
+    while i64::try_from(mantissa).is_err() && scale > 0 {
+        mantissa /= 10;
+        scale -= 1;
+    }
+    let price = i64::try_from(mantissa).map_err(|_| PredictionMarketError::ArithmeticOverflow)?;
+    let exponent = -(scale as i32);
+
+    market.oracle_data = (price, exponent, round.num_success, round.round_open_timestamp).try_to_vec()?;
+
+    let (winning_outcome, resolved_value) = settle_from_price(
+        market,
+        &mut ctx.accounts.market_resolution,
+        &ctx.accounts.vault,
+        &ctx.accounts.protocol_fee_account,
+        &ctx.accounts.token_program,
+        ctx.accounts.resolver.key(),
+        price,
+        exponent,
+        clock.unix_timestamp,
+    )?;
+
+    // Price feeds settle without the oracle; it only earns a resolution when
+    // it submits one itself
+    if ctx.accounts.resolver.key() == market.oracle {
+        record_oracle_resolution(&ctx.accounts.oracle_registry, &market.oracle, true)?;
+    }
+
+    emit!(SwitchboardResolvedEvent {
+        market: market.key(),
+        aggregator: ctx.accounts.aggregator.key(),
+        price,
+        exponent,
+        num_success: round.num_success,
+        round_open_timestamp: round.round_open_timestamp,
+        winning_outcome,
+        resolved_value,
+        resolution_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SwitchboardResolvedEvent {
+    pub market: Pubkey,
+    pub aggregator: Pubkey,
+    pub price: i64,
+    pub exponent: i32,
+    pub num_success: u32,
+    pub round_open_timestamp: i64,
+    pub winning_outcome: Option<u8>,
+    pub resolved_value: Option<i64>,
+    pub resolution_timestamp: i64,
+}
@@ -1,41 +1,52 @@
-```rust
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::state::*;
 use crate::errors::*;
+use crate::math::{apply_bps, mul_div_u64, Rounding};
+use crate::utils::{accrue_fees, record_oracle_resolution, require_active_oracle, validate_oracle_signature};
 
 #[derive(Accounts)]
 pub struct ResolveMarket<'info> {
     #[account(
         mut,
-        seeds = [b"market", market.creator.as_ref(), &market.market_id.to_le_bytes()],
+        seeds = [b"market", market.market_id.as_bytes()],
         bump = market.bump,
         constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.oracle_committee.is_none() @ PredictionMarketError::UnauthorizedOracle,
+        constraint = market.price_feed.is_none() @ PredictionMarketError::UnauthorizedOracle,
         constraint = market.resolution_time <= Clock::get()?.unix_timestamp @ PredictionMarketError::MarketNotExpired
     )]
-    pub market: Account<'info, Market>,
+    pub market: Box<Account<'info, Market>>,
 
     #[account(
-        constraint = oracle.key() == market.oracle @ PredictionMarketError::InvalidOracle
+        mut,
+        seeds = [b"market_resolution", market.key().as_ref()],
+        bump = market_resolution.bump,
+        constraint = market_resolution.proposer.is_none() @ PredictionMarketError::DisputePeriodActive
     )]
-    pub oracle: Signer<'info>,
+    pub market_resolution: Box<Account<'info, MarketResolution>>,
+
+    /// CHECK: Registry PDA of the market oracle, which must be active if registered
+    /// and is credited with the resolution
+    #[account(mut)]
+    pub oracle_registry: UncheckedAccount<'info>,
+
+    /// The market oracle, or any relayer submitting a signed oracle attestation
+    pub resolver: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, used to introspect the Ed25519 verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
 
     #[account(
         mut,
-        seeds = [b"vault", market.key().as_ref()],
+        seeds = [b"market_vault", market.key().as_ref()],
         bump = market.vault_bump,
         token::mint = market.token_mint,
-        token::authority = vault_authority
+        token::authority = market
     )]
     pub vault: Account<'info, TokenAccount>,
 
-    /// CHECK: This is a PDA used as the vault authority
-    #[account(
-        seeds = [b"vault_authority", market.key().as_ref()],
-        bump = market.vault_authority_bump
-    )]
-    pub vault_authority: UncheckedAccount<'info>,
-
     #[account(
         mut,
         seeds = [b"protocol_fee", market.key().as_ref()],
@@ -56,70 +67,84 @@ pub struct ResolveMarket<'info> {
     pub system_program: Program<'info, System>,
 }
 
-impl<'info> ResolveMarket<'info> {
-    pub fn resolve_market(&mut self, outcome: u8, oracle_data: Vec<u8>) -> Result<()> {
-        let market = &mut self.market;
-        
-        // Validate outcome
-        require!(outcome < market.outcomes.len() as u8, PredictionMarketError::InvalidOutcome);
-        
-        // Validate oracle data if required
-        if !oracle_data.is_empty() {
-            require!(oracle_data.len() <= 256, PredictionMarketError::OracleDataTooLarge);
-        }
-
-        // Update market state
-        market.status = MarketStatus::Resolved;
-        market.winning_outcome = Some(outcome);
-        market.resolution_timestamp = Clock::get()?.unix_timestamp;
-        market.oracle_data = oracle_data;
-
-        // Calculate protocol fee
-        let total_pool = market.total_pool;
-        let protocol_fee = total_pool
-            .checked_mul(market.protocol_fee_rate as u64)
-            .ok_or(PredictionMarketError::MathOverflow)?
-            .checked_div(10000)
-            .ok_or(PredictionMarketError::MathOverflow)?;
-
-        // Transfer protocol fee
-        if protocol_fee > 0 {
-            let seeds = &[
-                b"vault_authority",
-                market.key().as_ref(),
-                &[market.vault_authority_bump]
-            ];
-            let signer_seeds = &[&seeds[..]];
-
-            let transfer_ctx = CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                Transfer {
-                    from: self.vault.to_account_info(),
-                    to: self.protocol_fee_account.to_account_info(),
-                    authority: self.vault_authority.to_account_info(),
-                },
-                signer_seeds,
-            );
-
-            token::transfer(transfer_ctx, protocol_fee)?;
-        }
-
-        // Update winning pool amount (after protocol fee)
-        let winning_outcome_index = outcome as usize;
-        let winning_pool = market.outcome_pools[winning_outcome_index];
-        let remaining_pool = total_pool.checked_sub(protocol_fee).ok_or(PredictionMarketError::MathOverflow)?;
-        
-        // Calculate payout ratio for winners
-        if winning_pool > 0 {
-            market.payout_ratio = remaining_pool
-                .checked_mul(10000)
-                .ok_or(PredictionMarketError::MathOverflow)?
-                .checked_div(winning_pool)
-                .ok_or(PredictionMarketError::MathOverflow)?;
-        } else {
-            // No winners, all funds go to protocol (already transferred above)
-            market.payout_ratio = 0;
-        }
+pub fn handler(ctx: Context<ResolveMarket>, outcome: u8, oracle_data: Vec<u8>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    // A deactivated oracle can no longer settle markets; every resolution it
+    // makes counts toward its track record
+    require_active_oracle(&ctx.accounts.oracle_registry, &market.oracle)?;
+    record_oracle_resolution(&ctx.accounts.oracle_registry, &market.oracle, true)?;
+
+    // Validate outcome; scalar markets settle through resolve_scalar_market,
+    // but any market may be declared Invalid here
+    let invalid = outcome == Market::INVALID_OUTCOME;
+    require!(
+        invalid || market.market_type == MarketType::Categorical,
+        PredictionMarketError::InvalidResolutionData
+    );
+    require!(
+        invalid || outcome < market.outcomes.len() as u8,
+        PredictionMarketError::InvalidOutcome
+    );
+
+    // Validate oracle data if required
+    if !oracle_data.is_empty() {
+        require!(
+            oracle_data.len() <= Market::MAX_ORACLE_DATA_LENGTH,
+            PredictionMarketError::InvalidResolutionData
+        );
+    }
+
+    // Anyone other than the oracle must relay an attestation signed by it
+    if ctx.accounts.resolver.key() != market.oracle {
+        let attestation = OracleAttestation::try_from_slice(&oracle_data)
+            .map_err(|_| PredictionMarketError::InvalidResolutionData)?;
+        require!(
+            attestation.nonce > ctx.accounts.market_resolution.attestation_nonce,
+            PredictionMarketError::NonceAlreadyUsed
+        );
+
+        validate_oracle_signature(
+            &ctx.accounts.instructions_sysvar.to_account_info(),
+            &market.oracle,
+            &market.key(),
+            outcome,
+            attestation.timestamp,
+            attestation.nonce,
+            clock.unix_timestamp,
+        )?;
+
+        ctx.accounts.market_resolution.attestation_nonce = attestation.nonce;
+    }
+
+    market.oracle_data = oracle_data;
+
+    if invalid {
+        let (total_pool, protocol_fee) = settle_invalid(
+            market,
+            &ctx.accounts.vault,
+            &ctx.accounts.protocol_fee_account,
+            &ctx.accounts.token_program,
+            clock.unix_timestamp,
+        )?;
+
+        emit!(MarketInvalidatedEvent {
+            market: market.key(),
+            total_pool,
+            payout_ratio: market.payout_ratio,
+            protocol_fee,
+            resolution_timestamp: clock.unix_timestamp,
+        });
+    } else {
+        let (total_pool, winning_pool, protocol_fee) = settle_categorical(
+            market,
+            &ctx.accounts.vault,
+            &ctx.accounts.protocol_fee_account,
+            &ctx.accounts.token_program,
+            outcome,
+            clock.unix_timestamp,
+        )?;
 
         emit!(MarketResolvedEvent {
             market: market.key(),
@@ -128,11 +153,128 @@ impl<'info> ResolveMarket<'info> {
             winning_pool,
             payout_ratio: market.payout_ratio,
             protocol_fee,
-            resolution_timestamp: market.resolution_timestamp,
+            resolution_timestamp: clock.unix_timestamp,
         });
+    }
+
+    // Record the resolution
+    let market_resolution = &mut ctx.accounts.market_resolution;
+    market_resolution.resolved = true;
+    market_resolution.resolution_time = clock.unix_timestamp;
+    market_resolution.winning_outcome = market.winning_outcome;
+    market_resolution.resolver = Some(ctx.accounts.resolver.key());
+    if invalid {
+        market_resolution.resolution_data = "invalid".to_string();
+    }
 
-        Ok(())
+    Ok(())
+}
+
+/// Resolve a categorical market to `outcome`: takes the protocol fee from the
+/// parimutuel pool and fixes the payout ratio for winning stakes.
+///
+/// Returns the total pool, the winning pool and the protocol fee taken.
+pub fn settle_categorical<'info>(
+    market: &mut Account<'info, Market>,
+    vault: &Account<'info, TokenAccount>,
+    protocol_fee_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    outcome: u8,
+    timestamp: i64,
+) -> Result<(u64, u64, u64)> {
+    // Update market state
+    market.status = MarketStatus::Resolved;
+    market.winning_outcome = Some(outcome);
+    market.resolved_at = Some(timestamp);
+    market.open_claims(timestamp);
+
+    let (total_pool, protocol_fee) = take_protocol_fee(market, vault, protocol_fee_account, token_program)?;
+
+    // Update winning pool amount (after protocol fee)
+    let winning_outcome_index = outcome as usize;
+    let winning_pool = market.outcomes[winning_outcome_index].total_amount;
+    let remaining_pool = total_pool.checked_sub(protocol_fee).ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    // Calculate payout ratio for winners
+    if winning_pool > 0 {
+        market.payout_ratio = mul_div_u64(remaining_pool, 10000, winning_pool, Rounding::Down)?;
+    } else {
+        // No winners, the remaining pool stays in the vault
+        market.payout_ratio = 0;
+    }
+
+    Ok((total_pool, winning_pool, protocol_fee))
+}
+
+/// Resolve a market Invalid: takes the same protocol fee as a normal
+/// resolution and refunds every parimutuel stake pro rata from the rest.
+/// LMSR positions are refunded pro rata from the collateral left in the vault;
+/// AMM positions and outcome tokens at an equal split.
+///
+/// Returns the total pool and the protocol fee taken.
+pub fn settle_invalid<'info>(
+    market: &mut Account<'info, Market>,
+    vault: &Account<'info, TokenAccount>,
+    protocol_fee_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    timestamp: i64,
+) -> Result<(u64, u64)> {
+    market.status = MarketStatus::Resolved;
+    market.winning_outcome = None;
+    market.resolved_invalid = true;
+    market.resolved_at = Some(timestamp);
+    market.open_claims(timestamp);
+
+    let (total_pool, protocol_fee) = take_protocol_fee(market, vault, protocol_fee_account, token_program)?;
+
+    // Every stake is refunded at the same ratio
+    let remaining_pool = total_pool.checked_sub(protocol_fee).ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    market.payout_ratio = if total_pool > 0 {
+        mul_div_u64(remaining_pool, 10000, total_pool, Rounding::Down)?
+    } else {
+        0
+    };
+
+    Ok((total_pool, protocol_fee))
+}
+
+/// Transfer the protocol fee on the parimutuel pool out of the market vault.
+///
+/// Returns the total pool and the fee taken.
+fn take_protocol_fee<'info>(
+    market: &mut Account<'info, Market>,
+    vault: &Account<'info, TokenAccount>,
+    protocol_fee_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+) -> Result<(u64, u64)> {
+    // Calculate protocol fee, charged to the pool and so rounded up
+    let total_pool: u64 = market.outcomes.iter().map(|o| o.total_amount).sum();
+    let protocol_fee = apply_bps(total_pool, market.platform_fee_bps as u64, Rounding::Up)?;
+
+    // Transfer protocol fee
+    if protocol_fee > 0 {
+        let seeds = &[
+            b"market",
+            market.market_id.as_bytes(),
+            &[market.bump]
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: vault.to_account_info(),
+                to: protocol_fee_account.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        token::transfer(transfer_ctx, protocol_fee)?;
+        accrue_fees(market, 0, protocol_fee, 0)?;
     }
+
+    Ok((total_pool, protocol_fee))
 }
 
 #[event]
@@ -145,4 +287,12 @@ pub struct MarketResolvedEvent {
     pub protocol_fee: u64,
     pub resolution_timestamp: i64,
 }
-```
\ No newline at end of file
+
+#[event]
+pub struct MarketInvalidatedEvent {
+    pub market: Pubkey,
+    pub total_pool: u64,
+    pub payout_ratio: u64,
+    pub protocol_fee: u64,
+    pub resolution_timestamp: i64,
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::utils::{record_oracle_resolution, require_active_oracle};
+
+#[derive(Accounts)]
+pub struct ResolveScalarMarket<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.market_type == MarketType::Scalar @ PredictionMarketError::InvalidResolutionData,
+        constraint = market.price_feed.is_none() @ PredictionMarketError::UnauthorizedOracle,
+        constraint = market.resolution_time <= Clock::get()?.unix_timestamp @ PredictionMarketError::MarketNotExpired
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_resolution", market.key().as_ref()],
+        bump = market_resolution.bump,
+        constraint = market_resolution.proposer.is_none() @ PredictionMarketError::DisputePeriodActive
+    )]
+    pub market_resolution: Box<Account<'info, MarketResolution>>,
+
+    /// CHECK: Registry PDA of the market oracle, which must be active if registered
+    /// and is credited with the resolution
+    #[account(mut)]
+    pub oracle_registry: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = oracle.key() == market.oracle @ PredictionMarketError::UnauthorizedOracle
+    )]
+    pub oracle: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ResolveScalarMarket>, value: i64, oracle_data: Vec<u8>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    // A deactivated oracle can no longer settle markets; every resolution it
+    // makes counts toward its track record
+    require_active_oracle(&ctx.accounts.oracle_registry, &market.oracle)?;
+    record_oracle_resolution(&ctx.accounts.oracle_registry, &market.oracle, true)?;
+
+    require!(
+        oracle_data.len() <= Market::MAX_ORACLE_DATA_LENGTH,
+        PredictionMarketError::InvalidResolutionData
+    );
+
+    settle_scalar(market, value, clock.unix_timestamp);
+    market.oracle_data = oracle_data;
+
+    // Record the resolution
+    let market_resolution = &mut ctx.accounts.market_resolution;
+    market_resolution.resolved = true;
+    market_resolution.resolution_time = clock.unix_timestamp;
+    market_resolution.winning_outcome = None;
+    market_resolution.resolver = Some(ctx.accounts.oracle.key());
+    market_resolution.resolution_data = value.to_string();
+
+    emit!(ScalarMarketResolvedEvent {
+        market: market.key(),
+        reported_value: value,
+        resolved_value: market.resolved_value.unwrap_or(value),
+        long_payout_bps: market.payout_bps(0),
+        short_payout_bps: market.payout_bps(1),
+        resolution_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Resolve a scalar market at `value`, clamped to the market's bounds
+pub fn settle_scalar(market: &mut Market, value: i64, timestamp: i64) {
+    market.resolved_value = Some(value.clamp(market.lower_bound, market.upper_bound));
+    market.status = MarketStatus::Resolved;
+    market.resolved_at = Some(timestamp);
+    market.open_claims(timestamp);
+}
+
+#[event]
+pub struct ScalarMarketResolvedEvent {
+    pub market: Pubkey,
+    pub reported_value: i64,
+    pub resolved_value: i64,
+    pub long_payout_bps: u64,
+    pub short_payout_bps: u64,
+    pub resolution_timestamp: i64,
+}
@@ -0,0 +1,211 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::cpmm;
+use crate::math::{mul_div, Rounding};
+use crate::invariant::assert_pool_vault_solvent;
+use super::buy_outcome::update_pool_prices;
+
+#[derive(Accounts)]
+#[instruction(outcome: u8)]
+pub struct SellOutcome<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.paused @ PredictionMarketError::MarketPaused
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = Clock::get()?.unix_timestamp < market.end_time @ PredictionMarketError::MarketExpired,
+        constraint = market.outcomes.len() == 2 @ PredictionMarketError::InvalidOutcomeCount,
+        constraint = outcome < 2 @ PredictionMarketError::InvalidOutcome
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_stats", market.key().as_ref()],
+        bump = market_stats.bump
+    )]
+    pub market_stats: Box<Account<'info, MarketStats>>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_pool", market.key().as_ref()],
+        bump = liquidity_pool.bump,
+        has_one = market,
+        has_one = liquidity_vault
+    )]
+    pub liquidity_pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(mut)]
+    pub liquidity_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"amm_position", market.key().as_ref(), trader.key().as_ref(), &[outcome]],
+        bump = position.bump,
+        constraint = position.owner == trader.key() @ PredictionMarketError::NoPosition
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(
+        mut,
+        constraint = trader_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = trader_token_account.owner == trader.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub trader_token_account: Account<'info, TokenAccount>,
+
+    pub trader: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(
+    ctx: Context<SellOutcome>,
+    outcome: u8,
+    return_amount: u64,
+    max_cost: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let market_stats = &mut ctx.accounts.market_stats;
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+    let position = &mut ctx.accounts.position;
+    let clock = Clock::get()?;
+
+    require!(return_amount > 0, PredictionMarketError::InvalidAmount);
+
+    // Gross up the requested collateral so the pool fee is retained for LPs
+    let fee_rate_bps = liquidity_pool.fee_rate_bps as u128;
+    let amount_plus_fee = mul_div(return_amount as u128, 10000, 10000 - fee_rate_bps, Rounding::Up)? as u64;
+    let fee = amount_plus_fee
+        .checked_sub(return_amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    let (sold_reserve, other_reserve) = if outcome == 0 {
+        (liquidity_pool.yes_reserves, liquidity_pool.no_reserves)
+    } else {
+        (liquidity_pool.no_reserves, liquidity_pool.yes_reserves)
+    };
+    let swap = cpmm::sell(sold_reserve, other_reserve, amount_plus_fee)?;
+    let shares_in = swap.shares;
+
+    // `max_cost` bounds the shares given up for `return_amount` of collateral
+    require!(shares_in <= max_cost, PredictionMarketError::SlippageExceeded);
+    require!(shares_in <= position.shares, PredictionMarketError::InsufficientTokenBalance);
+
+    // Transfer collateral from the pool to the trader
+    let market_key = market.key();
+    let seeds = &[
+        b"liquidity_pool",
+        market_key.as_ref(),
+        &[liquidity_pool.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.liquidity_vault.to_account_info(),
+            to: ctx.accounts.trader_token_account.to_account_info(),
+            authority: liquidity_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, return_amount)?;
+
+    // Update pool reserves
+    if outcome == 0 {
+        liquidity_pool.yes_reserves = swap.traded_reserve;
+        liquidity_pool.no_reserves = swap.other_reserve;
+    } else {
+        liquidity_pool.no_reserves = swap.traded_reserve;
+        liquidity_pool.yes_reserves = swap.other_reserve;
+    }
+    liquidity_pool.k_constant = (liquidity_pool.yes_reserves as u128)
+        .checked_mul(liquidity_pool.no_reserves as u128)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    let outstanding = liquidity_pool.shares_outstanding_mut(outcome);
+    *outstanding = outstanding
+        .checked_sub(shares_in)
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+    liquidity_pool.accrue_lp_fees(fee)?;
+    liquidity_pool.update_yield_metrics(clock.unix_timestamp);
+    liquidity_pool.last_updated = clock.unix_timestamp;
+
+    // Realize PnL against the average entry price
+    let cost_basis = position.cost_basis(shares_in)?;
+    position.realized_pnl = position.realized_pnl
+        .checked_add(return_amount as i64 - cost_basis as i64)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    position.shares = position.shares
+        .checked_sub(shares_in)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    position.last_updated = clock.unix_timestamp;
+
+    // Update prices and stats
+    update_pool_prices(liquidity_pool, market_stats, outcome)?;
+
+    market_stats.total_volume = market_stats.total_volume
+        .checked_add(return_amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    if outcome == 0 {
+        market_stats.yes_volume = market_stats.yes_volume
+            .checked_add(return_amount)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    } else {
+        market_stats.no_volume = market_stats.no_volume
+            .checked_add(return_amount)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    }
+
+    market_stats.total_trades = market_stats.total_trades
+        .checked_add(1)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    market.total_volume = market.total_volume
+        .checked_add(return_amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.total_volume = global_state.total_volume
+        .checked_add(return_amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(OutcomeSoldEvent {
+        market: market.key(),
+        trader: ctx.accounts.trader.key(),
+        outcome,
+        return_amount,
+        fee,
+        shares_in,
+        yes_price: market_stats.yes_price,
+        no_price: market_stats.no_price,
+        timestamp: clock.unix_timestamp,
+    });
+
+    assert_pool_vault_solvent(&ctx.accounts.market, &ctx.accounts.liquidity_pool, &mut ctx.accounts.liquidity_vault)?;
+
+    Ok(())
+}
+
+#[event]
+pub struct OutcomeSoldEvent {
+    pub market: Pubkey,
+    pub trader: Pubkey,
+    pub outcome: u8,
+    pub return_amount: u64,
+    pub fee: u64,
+    pub shares_in: u64,
+    pub yes_price: u64,
+    pub no_price: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::lmsr;
+use crate::utils::{accrue_fees, calculate_trading_fees};
+use crate::invariant::assert_market_vault_solvent;
+use super::buy_shares::update_prices;
+
+#[derive(Accounts)]
+#[instruction(outcome: u8)]
+pub struct SellShares<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.paused @ PredictionMarketError::MarketPaused
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = Clock::get()?.unix_timestamp < market.end_time @ PredictionMarketError::MarketExpired,
+        constraint = outcome < market.outcomes.len() as u8 @ PredictionMarketError::InvalidOutcome,
+        constraint = market.lmsr_b > 0 @ PredictionMarketError::FeatureNotEnabled
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_stats", market.key().as_ref()],
+        bump = market_stats.bump
+    )]
+    pub market_stats: Box<Account<'info, MarketStats>>,
+
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), trader.key().as_ref(), &[outcome]],
+        bump = position.bump,
+        constraint = position.owner == trader.key() @ PredictionMarketError::NoPosition
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(
+        mut,
+        constraint = trader_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = trader_token_account.owner == trader.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub trader_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_fee", market.key().as_ref()],
+        bump,
+        token::mint = market.token_mint
+    )]
+    pub protocol_fee_account: Account<'info, TokenAccount>,
+
+    pub trader: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<SellShares>, outcome: u8, shares: u64, min_proceeds: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let market_stats = &mut ctx.accounts.market_stats;
+    let position = &mut ctx.accounts.position;
+    let clock = Clock::get()?;
+    let index = outcome as usize;
+
+    require!(shares > 0, PredictionMarketError::InvalidAmount);
+    require!(shares <= position.shares, PredictionMarketError::InsufficientTokenBalance);
+
+    // Quote the trade against the LMSR cost function
+    let quantities: Vec<u64> = market.outcomes.iter().map(|o| o.total_shares).collect();
+    let proceeds = lmsr::sell_proceeds(&quantities, market.lmsr_b, index, shares)?;
+
+    // Trading fees are withheld from the proceeds
+    let (creator_fee, platform_fee, oracle_fee) = calculate_trading_fees(market, proceeds)?;
+    let fee = accrue_fees(market, creator_fee, platform_fee, oracle_fee)?;
+    let net_proceeds = proceeds.checked_sub(fee).ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    require!(net_proceeds >= min_proceeds, PredictionMarketError::SlippageExceeded);
+
+    // Transfer collateral from market vault to trader
+    let seeds = &[
+        b"market",
+        market.market_id.as_bytes(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.market_vault.to_account_info(),
+            to: ctx.accounts.trader_token_account.to_account_info(),
+            authority: market.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, net_proceeds)?;
+
+    if fee > 0 {
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.market_vault.to_account_info(),
+                to: ctx.accounts.protocol_fee_account.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, fee)?;
+    }
+
+    // Realize PnL against the average entry price
+    let cost_basis = position.cost_basis(shares)?;
+    position.realized_pnl = position.realized_pnl
+        .checked_add(net_proceeds as i64 - cost_basis as i64)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    position.shares = position.shares
+        .checked_sub(shares)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    position.last_updated = clock.unix_timestamp;
+
+    // Update outcome quantities and prices
+    market.outcomes[index].total_shares = market.outcomes[index]
+        .total_shares
+        .checked_sub(shares)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    market.outcomes[index].volume_24h = market.outcomes[index]
+        .volume_24h
+        .checked_add(proceeds)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    update_prices(market)?;
+
+    // Update market stats
+    market_stats.total_volume = market_stats.total_volume
+        .checked_add(proceeds)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    market_stats.total_trades = market_stats.total_trades
+        .checked_add(1)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    market_stats.last_trade_price = market.outcomes[index].price;
+
+    emit!(SharesSoldEvent {
+        market: market.key(),
+        trader: ctx.accounts.trader.key(),
+        outcome,
+        shares,
+        proceeds: net_proceeds,
+        fee,
+        new_price: market.outcomes[index].price,
+        timestamp: clock.unix_timestamp,
+    });
+
+    assert_market_vault_solvent(&ctx.accounts.market, None, &mut ctx.accounts.market_vault)?;
+
+    Ok(())
+}
+
+#[event]
+pub struct SharesSoldEvent {
+    pub market: Pubkey,
+    pub trader: Pubkey,
+    pub outcome: u8,
+    pub shares: u64,
+    pub proceeds: u64,
+    pub fee: u64,
+    pub new_price: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,183 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::math::{apply_bps, mul_div_u64, Rounding};
+use crate::invariant::assert_pool_vault_solvent;
+
+#[derive(Accounts)]
+pub struct SettleLiquidity<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.is_settled() @ PredictionMarketError::MarketNotResolved
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_pool", market.key().as_ref()],
+        bump = liquidity_pool.bump,
+        has_one = market,
+        has_one = liquidity_vault,
+        has_one = lp_token_mint,
+        constraint = liquidity_pool.settled @ PredictionMarketError::PoolNotSettled
+    )]
+    pub liquidity_pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_position", market.key().as_ref(), liquidity_provider.key().as_ref()],
+        bump = liquidity_position.bump,
+        constraint = liquidity_position.owner == liquidity_provider.key() @ PredictionMarketError::NoPosition,
+        has_one = market
+    )]
+    pub liquidity_position: Box<Account<'info, LiquidityPosition>>,
+
+    #[account(mut)]
+    pub liquidity_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub lp_token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = provider_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = provider_token_account.owner == liquidity_provider.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub provider_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = provider_lp_token_account.mint == lp_token_mint.key() @ PredictionMarketError::InvalidTokenMint,
+        constraint = provider_lp_token_account.owner == liquidity_provider.key() @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub provider_lp_token_account: Account<'info, TokenAccount>,
+
+    pub liquidity_provider: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<SettleLiquidity>, lp_tokens_to_burn: u64) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(lp_tokens_to_burn > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        lp_tokens_to_burn <= ctx.accounts.liquidity_position.lp_tokens,
+        PredictionMarketError::InsufficientTokenBalance
+    );
+    require!(
+        ctx.accounts.liquidity_pool.total_lp_tokens > 0,
+        PredictionMarketError::NoLiquidity
+    );
+
+    let market = &mut ctx.accounts.market;
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+    let liquidity_position = &mut ctx.accounts.liquidity_position;
+
+    // Exact share of the settled pool; the last LP out takes any rounding dust
+    let payout = mul_div_u64(
+        liquidity_pool.settlement_value,
+        lp_tokens_to_burn,
+        liquidity_pool.total_lp_tokens,
+        Rounding::Down,
+    )?;
+
+    // Value the burned share would have had if its deposits were simply held
+    let share_a = mul_div_u64(liquidity_position.outcome_a_contributed, lp_tokens_to_burn, liquidity_position.lp_tokens, Rounding::Down)?;
+    let share_b = mul_div_u64(liquidity_position.outcome_b_contributed, lp_tokens_to_burn, liquidity_position.lp_tokens, Rounding::Down)?;
+    let hold_value = apply_bps(share_a, market.payout_bps(0), Rounding::Down)?
+        .checked_add(apply_bps(share_b, market.payout_bps(1), Rounding::Down)?)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    let impermanent_loss = hold_value as i64 - payout as i64;
+
+    if payout > 0 {
+        let market_key = market.key();
+        let seeds = &[
+            b"liquidity_pool",
+            market_key.as_ref(),
+            &[liquidity_pool.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.liquidity_vault.to_account_info(),
+                to: ctx.accounts.provider_token_account.to_account_info(),
+                authority: liquidity_pool.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, payout)?;
+    }
+
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.lp_token_mint.to_account_info(),
+            from: ctx.accounts.provider_lp_token_account.to_account_info(),
+            authority: ctx.accounts.liquidity_provider.to_account_info(),
+        },
+    );
+    token::burn(burn_ctx, lp_tokens_to_burn)?;
+
+    // Update liquidity position, settling fees earned on the old balance
+    liquidity_position.settle_fees(liquidity_pool)?;
+    liquidity_position.outcome_a_contributed -= share_a;
+    liquidity_position.outcome_b_contributed -= share_b;
+    liquidity_position.lp_tokens = liquidity_position.lp_tokens
+        .checked_sub(lp_tokens_to_burn)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    liquidity_position.sync_fee_debt(liquidity_pool)?;
+    liquidity_position.deposited_amount = liquidity_position.deposited_amount
+        .saturating_sub(share_a.saturating_add(share_b));
+    liquidity_position.last_updated = clock.unix_timestamp;
+
+    // Update liquidity pool state
+    liquidity_pool.settlement_value = liquidity_pool.settlement_value
+        .checked_sub(payout)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    liquidity_pool.total_lp_tokens = liquidity_pool.total_lp_tokens
+        .checked_sub(lp_tokens_to_burn)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    liquidity_pool.total_liquidity = liquidity_pool.total_liquidity.saturating_sub(payout);
+    liquidity_pool.last_updated = clock.unix_timestamp;
+
+    market.total_liquidity = market.total_liquidity.saturating_sub(payout);
+
+    if liquidity_position.lp_tokens == 0 && liquidity_position.is_active {
+        liquidity_position.is_active = false;
+        liquidity_pool.active_providers = liquidity_pool.active_providers
+            .checked_sub(1)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    }
+
+    emit!(LiquiditySettledEvent {
+        market: market.key(),
+        liquidity_provider: ctx.accounts.liquidity_provider.key(),
+        lp_tokens_burned: lp_tokens_to_burn,
+        payout,
+        hold_value,
+        impermanent_loss,
+        remaining_lp_tokens: liquidity_position.lp_tokens,
+        timestamp: clock.unix_timestamp,
+    });
+
+    assert_pool_vault_solvent(&ctx.accounts.market, &ctx.accounts.liquidity_pool, &mut ctx.accounts.liquidity_vault)?;
+
+    Ok(())
+}
+
+#[event]
+pub struct LiquiditySettledEvent {
+    pub market: Pubkey,
+    pub liquidity_provider: Pubkey,
+    pub lp_tokens_burned: u64,
+    pub payout: u64,
+    pub hold_value: u64,
+    pub impermanent_loss: i64,
+    pub remaining_lp_tokens: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SettleOracleVote<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Resolved || market.status == MarketStatus::Swept
+            @ PredictionMarketError::MarketNotResolved
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"oracle_vote", market.key().as_ref(), oracle.key().as_ref()],
+        bump = oracle_vote.bump
+    )]
+    pub oracle_vote: Account<'info, OracleVote>,
+
+    #[account(
+        mut,
+        seeds = [b"oracle", oracle.authority.as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+
+    /// CHECK: Receives the vote account rent; must be the voting oracle's authority
+    #[account(
+        mut,
+        address = oracle.authority @ PredictionMarketError::InvalidAuthority
+    )]
+    pub authority: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<SettleOracleVote>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let oracle_vote = &ctx.accounts.oracle_vote;
+    let oracle = &mut ctx.accounts.oracle;
+
+    let accurate = market.winning_outcome == Some(oracle_vote.outcome);
+    oracle.record_resolution(accurate);
+
+    emit!(OracleVoteSettledEvent {
+        market: market.key(),
+        oracle: oracle.key(),
+        outcome: oracle_vote.outcome,
+        accurate,
+        reputation_score: oracle.reputation_score,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct OracleVoteSettledEvent {
+    pub market: Pubkey,
+    pub oracle: Pubkey,
+    pub outcome: u8,
+    pub accurate: bool,
+    pub reputation_score: u32,
+    pub timestamp: i64,
+}
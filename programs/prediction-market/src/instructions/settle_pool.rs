@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::math::{apply_bps, Rounding};
+use crate::invariant::{amm_share_liability, assert_market_vault_solvent, assert_pool_vault_solvent};
+
+#[derive(Accounts)]
+pub struct SettlePool<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        has_one = outcome_a_mint,
+        has_one = outcome_b_mint,
+        constraint = market.is_settled() @ PredictionMarketError::MarketNotResolved
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"outcome_tokens", market.key().as_ref()],
+        bump = outcome_tokens.bump,
+        has_one = market
+    )]
+    pub outcome_tokens: Box<Account<'info, OutcomeTokens>>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_pool", market.key().as_ref()],
+        bump = liquidity_pool.bump,
+        has_one = market,
+        has_one = liquidity_vault,
+        has_one = outcome_a_vault,
+        has_one = outcome_b_vault,
+        constraint = !liquidity_pool.settled @ PredictionMarketError::PoolAlreadySettled
+    )]
+    pub liquidity_pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(mut)]
+    pub liquidity_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub outcome_a_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub outcome_b_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub outcome_a_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub outcome_b_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    /// Anyone may settle the pool once the market is resolved or cancelled
+    pub caller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<SettlePool>) -> Result<()> {
+    let accounts = ctx.accounts;
+    let market = &accounts.market;
+    let clock = Clock::get()?;
+    let market_key = market.key();
+
+    let pool_seeds = &[
+        b"liquidity_pool",
+        market_key.as_ref(),
+        &[accounts.liquidity_pool.bump],
+    ];
+    let pool_signer = &[&pool_seeds[..]];
+
+    let market_seeds = &[
+        b"market",
+        market.market_id.as_bytes(),
+        &[market.bump],
+    ];
+    let market_signer = &[&market_seeds[..]];
+
+    // Redeem the pool's outcome tokens into the liquidity vault
+    let mut redeemed = 0u64;
+    let sides = [
+        (0u8, &accounts.outcome_a_vault, &accounts.outcome_a_mint),
+        (1u8, &accounts.outcome_b_vault, &accounts.outcome_b_mint),
+    ];
+    for (outcome, vault, mint) in sides {
+        let payout_bps = market.payout_bps(outcome);
+        let amount = vault.amount;
+        // Losing tokens are worthless and stay where they are
+        if amount == 0 || payout_bps == 0 {
+            continue;
+        }
+
+        let burn_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            Burn {
+                mint: mint.to_account_info(),
+                from: vault.to_account_info(),
+                authority: accounts.liquidity_pool.to_account_info(),
+            },
+            pool_signer,
+        );
+        token::burn(burn_ctx, amount)?;
+
+        let payout = apply_bps(amount, payout_bps, Rounding::Down)?;
+        let transfer_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            Transfer {
+                from: accounts.market_vault.to_account_info(),
+                to: accounts.liquidity_vault.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            market_signer,
+        );
+        token::transfer(transfer_ctx, payout)?;
+        redeemed = redeemed
+            .checked_add(payout)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+        let outcome_tokens = &mut accounts.outcome_tokens;
+        if outcome == 0 {
+            outcome_tokens.yes_token_supply = outcome_tokens.yes_token_supply
+                .checked_sub(amount)
+                .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+        } else {
+            outcome_tokens.no_token_supply = outcome_tokens.no_token_supply
+                .checked_sub(amount)
+                .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+        }
+    }
+
+    // The pool owns its reserves at the resolved payout; traders' shares are
+    // paid from the rest of the vault through claim_outcome
+    let traders_owed = amm_share_liability(market, &accounts.liquidity_pool)?;
+    let liquidity_pool = &mut accounts.liquidity_pool;
+    let reserve_value = apply_bps(liquidity_pool.yes_reserves, market.payout_bps(0), Rounding::Down)?
+        .checked_add(apply_bps(liquidity_pool.no_reserves, market.payout_bps(1), Rounding::Down)?)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    accounts.liquidity_vault.reload()?;
+    let available = accounts.liquidity_vault.amount
+        .saturating_sub(liquidity_pool.unclaimed_fees)
+        .saturating_sub(traders_owed);
+
+    liquidity_pool.settlement_value = reserve_value.min(available);
+    liquidity_pool.settled = true;
+    liquidity_pool.last_updated = clock.unix_timestamp;
+
+    emit!(PoolSettledEvent {
+        market: market_key,
+        settled_by: accounts.caller.key(),
+        redeemed,
+        settlement_value: liquidity_pool.settlement_value,
+        timestamp: clock.unix_timestamp,
+    });
+
+    assert_market_vault_solvent(&accounts.market, Some(&accounts.outcome_tokens), &mut accounts.market_vault)?;
+    assert_pool_vault_solvent(&accounts.market, &accounts.liquidity_pool, &mut accounts.liquidity_vault)?;
+
+    Ok(())
+}
+
+#[event]
+pub struct PoolSettledEvent {
+    pub market: Pubkey,
+    pub settled_by: Pubkey,
+    pub redeemed: u64,
+    pub settlement_value: u64,
+    pub timestamp: i64,
+}
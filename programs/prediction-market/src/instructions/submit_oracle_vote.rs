@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SubmitOracleVote<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.resolution_time <= Clock::get()?.unix_timestamp @ PredictionMarketError::MarketNotExpired
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"oracle_committee", market.key().as_ref()],
+        bump = oracle_committee.bump
+    )]
+    pub oracle_committee: Box<Account<'info, OracleCommittee>>,
+
+    #[account(
+        constraint = oracle.is_active @ PredictionMarketError::UnauthorizedOracle,
+        constraint = oracle.authority == authority.key() @ PredictionMarketError::UnauthorizedOracle,
+        constraint = oracle_committee.members.contains(&oracle.key()) @ PredictionMarketError::UnauthorizedOracle
+    )]
+    pub oracle: Box<Account<'info, Oracle>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = OracleVote::space(),
+        seeds = [b"oracle_vote", market.key().as_ref(), oracle.key().as_ref()],
+        bump
+    )]
+    pub oracle_vote: Box<Account<'info, OracleVote>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SubmitOracleVote>, outcome: u8) -> Result<()> {
+    let oracle_committee = &mut ctx.accounts.oracle_committee;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp < oracle_committee.vote_deadline,
+        PredictionMarketError::VotingPeriodNotActive
+    );
+    require!(
+        (outcome as usize) < oracle_committee.tallies.len(),
+        PredictionMarketError::InvalidOutcome
+    );
+
+    oracle_committee.tallies[outcome as usize] = oracle_committee.tallies[outcome as usize]
+        .checked_add(1)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    oracle_committee.total_votes = oracle_committee.total_votes
+        .checked_add(1)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    let oracle_vote = &mut ctx.accounts.oracle_vote;
+    oracle_vote.market = ctx.accounts.market.key();
+    oracle_vote.oracle = ctx.accounts.oracle.key();
+    oracle_vote.outcome = outcome;
+    oracle_vote.voted_at = clock.unix_timestamp;
+    oracle_vote.bump = ctx.bumps.oracle_vote;
+
+    emit!(OracleVoteSubmittedEvent {
+        market: oracle_vote.market,
+        oracle: oracle_vote.oracle,
+        outcome,
+        votes_for_outcome: oracle_committee.tallies[outcome as usize],
+        total_votes: oracle_committee.total_votes,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct OracleVoteSubmittedEvent {
+    pub market: Pubkey,
+    pub oracle: Pubkey,
+    pub outcome: u8,
+    pub votes_for_outcome: u8,
+    pub total_votes: u8,
+    pub timestamp: i64,
+}
@@ -0,0 +1,363 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, CloseAccount, Mint, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SweepMarket<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.is_settled() @ PredictionMarketError::MarketNotResolved,
+        constraint = Clock::get()?.unix_timestamp >= market.claim_deadline @ PredictionMarketError::MarketNotExpired
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_resolution", market.key().as_ref()],
+        bump = market_resolution.bump,
+        close = creator
+    )]
+    pub market_resolution: Box<Account<'info, MarketResolution>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_stats", market.key().as_ref()],
+        bump = market_stats.bump,
+        close = creator
+    )]
+    pub market_stats: Box<Account<'info, MarketStats>>,
+
+    #[account(
+        mut,
+        seeds = [b"outcome_tokens", market.key().as_ref()],
+        bump = outcome_tokens.bump,
+        has_one = market,
+        has_one = yes_token_vault,
+        has_one = no_token_vault,
+        close = creator
+    )]
+    pub outcome_tokens: Box<Account<'info, OutcomeTokens>>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_pool", market.key().as_ref()],
+        bump = liquidity_pool.bump,
+        has_one = market,
+        has_one = liquidity_vault,
+        close = creator
+    )]
+    pub liquidity_pool: Box<Account<'info, LiquidityPool>>,
+
+    /// Required when the market was configured for committee resolution
+    #[account(
+        mut,
+        seeds = [b"oracle_committee", market.key().as_ref()],
+        bump = oracle_committee.bump,
+        close = creator
+    )]
+    pub oracle_committee: Option<Box<Account<'info, OracleCommittee>>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub liquidity_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_fee", market.key().as_ref()],
+        bump,
+        token::mint = market.token_mint,
+        token::authority = protocol_fee_authority
+    )]
+    pub protocol_fee_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA used as the protocol fee authority
+    #[account(
+        seeds = [b"protocol_fee_authority"],
+        bump
+    )]
+    pub protocol_fee_authority: UncheckedAccount<'info>,
+
+    /// Required when a resolution proposal's bonds were never settled
+    #[account(
+        mut,
+        seeds = [b"resolution_bond", market.key().as_ref()],
+        bump
+    )]
+    pub bond_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub yes_token_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub no_token_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = market.outcome_a_mint @ PredictionMarketError::InvalidTokenMint
+    )]
+    pub outcome_a_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = market.outcome_b_mint @ PredictionMarketError::InvalidTokenMint
+    )]
+    pub outcome_b_mint: Account<'info, Mint>,
+
+    /// Required when the creator has unwithdrawn fees
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = creator_token_account.owner == market.creator @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub creator_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Required when the platform has unwithdrawn fees
+    #[account(
+        mut,
+        constraint = treasury.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = treasury.owner == global_state.authority @ PredictionMarketError::TreasuryMismatch
+    )]
+    pub treasury: Option<Account<'info, TokenAccount>>,
+
+    /// Required when the oracle has unwithdrawn fees
+    #[account(
+        mut,
+        constraint = oracle_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = oracle_token_account.owner == market.oracle @ PredictionMarketError::InvalidAccountOwner
+    )]
+    pub oracle_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Token account of the creator-chosen destination, or of the platform
+    #[account(
+        mut,
+        constraint = destination.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = destination.owner == market.sweep_destination.unwrap_or(global_state.authority)
+            @ PredictionMarketError::TreasuryMismatch
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    /// CHECK: Receives the rent of every closed account; must be the market creator
+    #[account(mut, address = market.creator @ PredictionMarketError::InvalidAuthority)]
+    pub creator: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<SweepMarket>) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &ctx.accounts.market;
+    let market_key = market.key();
+
+    let market_seeds = &[
+        b"market",
+        market.market_id.as_bytes(),
+        &[market.bump],
+    ];
+    let pool_seeds = &[
+        b"liquidity_pool",
+        market_key.as_ref(),
+        &[ctx.accounts.liquidity_pool.bump],
+    ];
+    let fee_authority_seeds = &[
+        b"protocol_fee_authority".as_ref(),
+        &[ctx.bumps.protocol_fee_authority],
+    ];
+
+    // Accrued fees are owed to their parties, not stranded, so they are paid
+    // out before the fee account is swept. Oracle fees on a cancelled market
+    // were never earned and go to the platform, as in withdraw_platform_fees.
+    let forfeited_oracle_fees = if market.status == MarketStatus::Cancelled {
+        market.oracle_fees_accrued
+    } else {
+        0
+    };
+    let platform_fees = market.platform_fees_accrued
+        .checked_add(forfeited_oracle_fees)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    let fee_payouts = [
+        (market.creator_fees_accrued, ctx.accounts.creator_token_account.as_ref()),
+        (platform_fees, ctx.accounts.treasury.as_ref()),
+        (market.oracle_fees_accrued - forfeited_oracle_fees, ctx.accounts.oracle_token_account.as_ref()),
+    ];
+    let fee_signer_seeds = &[&fee_authority_seeds[..]];
+    let mut fees_paid = 0u64;
+    for (amount, recipient) in fee_payouts {
+        if amount == 0 {
+            continue;
+        }
+        let recipient = recipient.ok_or(PredictionMarketError::TokenAccountNotFound)?;
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.protocol_fee_account.to_account_info(),
+                to: recipient.to_account_info(),
+                authority: ctx.accounts.protocol_fee_authority.to_account_info(),
+            },
+            fee_signer_seeds,
+        );
+        token::transfer(transfer_ctx, amount)?;
+        fees_paid = fees_paid
+            .checked_add(amount)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    }
+    ctx.accounts.global_state.total_fees_collected = ctx.accounts.global_state.total_fees_collected
+        .checked_add(platform_fees)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    let fee_account_amount = ctx.accounts.protocol_fee_account.amount
+        .checked_sub(fees_paid)
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+
+    // Outcome tokens only reach the pool's outcome vaults by donation; they
+    // are burned so the vaults can be closed
+    let outcome_vaults = [
+        (&ctx.accounts.yes_token_vault, &ctx.accounts.outcome_a_mint),
+        (&ctx.accounts.no_token_vault, &ctx.accounts.outcome_b_mint),
+    ];
+    for (vault, mint) in outcome_vaults {
+        let signer_seeds = &[&pool_seeds[..]];
+
+        if vault.amount > 0 {
+            let burn_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: mint.to_account_info(),
+                    from: vault.to_account_info(),
+                    authority: ctx.accounts.liquidity_pool.to_account_info(),
+                },
+                signer_seeds,
+            );
+            token::burn(burn_ctx, vault.amount)?;
+        }
+
+        let close_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: vault.to_account_info(),
+                destination: ctx.accounts.creator.to_account_info(),
+                authority: ctx.accounts.liquidity_pool.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::close_account(close_ctx)?;
+    }
+
+    // Empty each collateral vault into the destination and close it under its
+    // own PDA authority
+    let vaults = [
+        (Some(&ctx.accounts.market_vault), ctx.accounts.market_vault.amount, market.to_account_info(), &market_seeds[..]),
+        (Some(&ctx.accounts.liquidity_vault), ctx.accounts.liquidity_vault.amount, ctx.accounts.liquidity_pool.to_account_info(), &pool_seeds[..]),
+        (Some(&ctx.accounts.protocol_fee_account), fee_account_amount, ctx.accounts.protocol_fee_authority.to_account_info(), &fee_authority_seeds[..]),
+        (ctx.accounts.bond_vault.as_ref(), ctx.accounts.bond_vault.as_ref().map_or(0, |v| v.amount), market.to_account_info(), &market_seeds[..]),
+    ];
+    let mut swept = [0u64; 4];
+    for (index, (vault, amount, authority, seeds)) in vaults.into_iter().enumerate() {
+        let Some(vault) = vault else { continue };
+        let signer_seeds = &[seeds];
+
+        if amount > 0 {
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: vault.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: authority.clone(),
+                },
+                signer_seeds,
+            );
+            token::transfer(transfer_ctx, amount)?;
+        }
+
+        let close_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: vault.to_account_info(),
+                destination: ctx.accounts.creator.to_account_info(),
+                authority,
+            },
+            signer_seeds,
+        );
+        token::close_account(close_ctx)?;
+
+        swept[index] = amount;
+    }
+
+    let [market_vault_amount, liquidity_vault_amount, fee_amount, bond_amount] = swept;
+    let total_swept = market_vault_amount
+        .checked_add(liquidity_vault_amount)
+        .and_then(|v| v.checked_add(fee_amount))
+        .and_then(|v| v.checked_add(bond_amount))
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(MarketSweptEvent {
+        market: market_key,
+        destination: ctx.accounts.destination.key(),
+        market_vault_amount,
+        liquidity_vault_amount,
+        fee_amount,
+        bond_amount,
+        total_swept,
+        fees_paid,
+        total_claimed: market.total_claimed,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // The market account stays behind as a tombstone: re-creating its
+    // market_id would revive every unclaimed Bet and Position PDA derived
+    // from it, and leftover positions and order books are still closed
+    // against it. Everything else is dropped and the account shrunk.
+    let market = &mut ctx.accounts.market;
+    market.status = MarketStatus::Swept;
+    market.creator_fees_accrued = 0;
+    market.platform_fees_accrued = 0;
+    market.oracle_fees_accrued = 0;
+    market.title.clear();
+    market.description.clear();
+    market.category.clear();
+    market.resolution_source.clear();
+    market.oracle_data.clear();
+    for outcome in market.outcomes.iter_mut() {
+        outcome.title.clear();
+    }
+
+    let size = 8 + market.try_to_vec()?.len();
+    let market_info = market.to_account_info();
+    let excess = market_info.lamports()
+        .checked_sub(Rent::get()?.minimum_balance(size))
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+    market_info.realloc(size, false)?;
+    **market_info.try_borrow_mut_lamports()? -= excess;
+    **ctx.accounts.creator.try_borrow_mut_lamports()? += excess;
+
+    Ok(())
+}
+
+#[event]
+pub struct MarketSweptEvent {
+    pub market: Pubkey,
+    pub destination: Pubkey,
+    pub market_vault_amount: u64,
+    pub liquidity_vault_amount: u64,
+    pub fee_amount: u64,
+    pub bond_amount: u64,
+    pub total_swept: u64,
+    pub fees_paid: u64,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
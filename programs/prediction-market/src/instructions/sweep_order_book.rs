@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SweepOrderBook<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Swept @ PredictionMarketError::MarketNotSwept
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"order_book", market.key().as_ref(), &[order_book.outcome_id]],
+        bump = order_book.bump,
+        has_one = market,
+        has_one = book_vault,
+        close = creator
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+
+    #[account(mut)]
+    pub book_vault: Account<'info, TokenAccount>,
+
+    /// Token account of the creator-chosen destination, or of the platform
+    #[account(
+        mut,
+        constraint = destination.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = destination.owner == market.sweep_destination.unwrap_or(global_state.authority)
+            @ PredictionMarketError::TreasuryMismatch
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    /// CHECK: Receives the rent of the closed accounts; must be the market creator
+    #[account(mut, address = market.creator @ PredictionMarketError::InvalidAuthority)]
+    pub creator: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<SweepOrderBook>) -> Result<()> {
+    let order_book = &ctx.accounts.order_book;
+    let market_key = ctx.accounts.market.key();
+    let amount = ctx.accounts.book_vault.amount;
+
+    let seeds = &[
+        b"order_book",
+        market_key.as_ref(),
+        &[order_book.outcome_id],
+        &[order_book.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Collateral of bids left resting past the claim deadline is swept with
+    // the rest of the market's unclaimed funds
+    if amount > 0 {
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.book_vault.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: order_book.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, amount)?;
+    }
+
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.book_vault.to_account_info(),
+            destination: ctx.accounts.creator.to_account_info(),
+            authority: order_book.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::close_account(close_ctx)?;
+
+    emit!(OrderBookSweptEvent {
+        market: market_key,
+        order_book: order_book.key(),
+        outcome: order_book.outcome_id,
+        destination: ctx.accounts.destination.key(),
+        amount,
+        resting_orders: (order_book.bids.len() + order_book.asks.len()) as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct OrderBookSweptEvent {
+    pub market: Pubkey,
+    pub order_book: Pubkey,
+    pub outcome: u8,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub resting_orders: u32,
+    pub timestamp: i64,
+}
@@ -0,0 +1,193 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct UpdateMarket<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        seeds = [b"market_stats", market.key().as_ref()],
+        bump = market_stats.bump
+    )]
+    pub market_stats: Box<Account<'info, MarketStats>>,
+
+    #[account(
+        constraint = authority.key() == market.creator || authority.key() == global_state.authority
+            @ PredictionMarketError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handler(
+    ctx: Context<UpdateMarket>,
+    title: Option<String>,
+    description: Option<String>,
+    category: Option<String>,
+    resolution_source: Option<String>,
+    min_bet_amount: Option<u64>,
+    max_bet_amount: Option<u64>,
+    end_time: Option<i64>,
+    sweep_destination: Option<Pubkey>,
+) -> Result<()> {
+    let global_state = &ctx.accounts.global_state;
+    let market = &mut ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp < market.end_time,
+        PredictionMarketError::MarketExpired
+    );
+
+    // Once the market has traded only extensions and tighter limits are allowed
+    let has_traded = ctx.accounts.market_stats.total_trades > 0;
+
+    let old_title = market.title.clone();
+    let old_description = market.description.clone();
+    let old_category = market.category.clone();
+    let old_resolution_source = market.resolution_source.clone();
+    let old_min_bet_amount = market.min_bet_amount;
+    let old_max_bet_amount = market.max_bet_amount;
+    let old_end_time = market.end_time;
+    let old_sweep_destination = market.sweep_destination;
+
+    if let Some(title) = title {
+        require!(!has_traded, PredictionMarketError::ConfigurationLocked);
+        require!(title.len() <= Market::MAX_TITLE_LENGTH, PredictionMarketError::MarketTitleTooLong);
+        market.title = title;
+    }
+
+    if let Some(description) = description {
+        require!(!has_traded, PredictionMarketError::ConfigurationLocked);
+        require!(
+            description.len() <= Market::MAX_DESCRIPTION_LENGTH,
+            PredictionMarketError::MarketDescriptionTooLong
+        );
+        market.description = description;
+    }
+
+    if let Some(category) = category {
+        require!(!has_traded, PredictionMarketError::ConfigurationLocked);
+        require!(category.len() <= Market::MAX_CATEGORY_LENGTH, PredictionMarketError::InvalidCategory);
+        market.category = category;
+    }
+
+    if let Some(resolution_source) = resolution_source {
+        require!(!has_traded, PredictionMarketError::ConfigurationLocked);
+        require!(
+            resolution_source.len() <= Market::MAX_RESOLUTION_SOURCE_LENGTH,
+            PredictionMarketError::InvalidMarketMetadata
+        );
+        market.resolution_source = resolution_source;
+    }
+
+    if let Some(min_bet_amount) = min_bet_amount {
+        require!(
+            !has_traded || min_bet_amount >= old_min_bet_amount,
+            PredictionMarketError::ConfigurationLocked
+        );
+        require!(min_bet_amount > 0, PredictionMarketError::InvalidBetAmount);
+        market.min_bet_amount = min_bet_amount;
+    }
+
+    if let Some(max_bet_amount) = max_bet_amount {
+        require!(
+            !has_traded || max_bet_amount <= old_max_bet_amount,
+            PredictionMarketError::ConfigurationLocked
+        );
+        market.max_bet_amount = max_bet_amount;
+    }
+
+    require!(
+        market.max_bet_amount >= market.min_bet_amount,
+        PredictionMarketError::InvalidBetAmount
+    );
+
+    if let Some(end_time) = end_time {
+        require!(
+            !has_traded || end_time >= old_end_time,
+            PredictionMarketError::ConfigurationLocked
+        );
+        let duration = end_time
+            .checked_sub(clock.unix_timestamp)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        require!(duration >= global_state.min_market_duration, PredictionMarketError::InvalidMarketDuration);
+        require!(duration <= global_state.max_market_duration, PredictionMarketError::InvalidMarketDuration);
+
+        market.end_time = end_time;
+
+        // Resolution can never happen before betting closes
+        if market.resolution_time < end_time {
+            market.resolution_time = end_time;
+        }
+    }
+
+    // Only the creator may redirect swept funds away from the platform
+    if let Some(sweep_destination) = sweep_destination {
+        require!(
+            ctx.accounts.authority.key() == market.creator,
+            PredictionMarketError::InvalidAuthority
+        );
+        market.sweep_destination = Some(sweep_destination);
+    }
+
+    emit!(MarketUpdatedEvent {
+        market: market.key(),
+        updated_by: ctx.accounts.authority.key(),
+        old_title,
+        new_title: market.title.clone(),
+        old_description,
+        new_description: market.description.clone(),
+        old_category,
+        new_category: market.category.clone(),
+        old_resolution_source,
+        new_resolution_source: market.resolution_source.clone(),
+        old_min_bet_amount,
+        new_min_bet_amount: market.min_bet_amount,
+        old_max_bet_amount,
+        new_max_bet_amount: market.max_bet_amount,
+        old_end_time,
+        new_end_time: market.end_time,
+        old_sweep_destination,
+        new_sweep_destination: market.sweep_destination,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MarketUpdatedEvent {
+    pub market: Pubkey,
+    pub updated_by: Pubkey,
+    pub old_title: String,
+    pub new_title: String,
+    pub old_description: String,
+    pub new_description: String,
+    pub old_category: String,
+    pub new_category: String,
+    pub old_resolution_source: String,
+    pub new_resolution_source: String,
+    pub old_min_bet_amount: u64,
+    pub new_min_bet_amount: u64,
+    pub old_max_bet_amount: u64,
+    pub new_max_bet_amount: u64,
+    pub old_end_time: i64,
+    pub new_end_time: i64,
+    pub old_sweep_destination: Option<Pubkey>,
+    pub new_sweep_destination: Option<Pubkey>,
+    pub timestamp: i64,
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct UpdatePlatform<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ PredictionMarketError::AdminPrivilegesRequired
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdatePlatform>,
+    platform_fee_rate: Option<u16>,
+    oracle_fee_rate: Option<u16>,
+    min_market_duration: Option<i64>,
+    max_market_duration: Option<i64>,
+    paused: Option<bool>,
+    min_oracle_reputation: Option<u32>,
+) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+
+    if let Some(rate) = platform_fee_rate {
+        require!(
+            rate <= GlobalState::MAX_PLATFORM_FEE_RATE,
+            PredictionMarketError::InvalidFeePercentage
+        );
+        global_state.platform_fee_rate = rate;
+    }
+
+    if let Some(rate) = oracle_fee_rate {
+        require!(
+            rate <= GlobalState::MAX_ORACLE_FEE_RATE,
+            PredictionMarketError::InvalidFeePercentage
+        );
+        global_state.oracle_fee_rate = rate;
+    }
+
+    if let Some(duration) = min_market_duration {
+        global_state.min_market_duration = duration;
+    }
+
+    if let Some(duration) = max_market_duration {
+        global_state.max_market_duration = duration;
+    }
+
+    require!(
+        global_state.min_market_duration > 0
+            && global_state.max_market_duration >= global_state.min_market_duration,
+        PredictionMarketError::InvalidMarketDuration
+    );
+
+    if let Some(paused) = paused {
+        global_state.paused = paused;
+    }
+
+    if let Some(reputation) = min_oracle_reputation {
+        require!(
+            reputation <= Oracle::MAX_REPUTATION,
+            PredictionMarketError::InvalidConfiguration
+        );
+        global_state.min_oracle_reputation = reputation;
+    }
+
+    emit!(PlatformUpdatedEvent {
+        authority: global_state.authority,
+        platform_fee_rate: global_state.platform_fee_rate,
+        oracle_fee_rate: global_state.oracle_fee_rate,
+        min_market_duration: global_state.min_market_duration,
+        max_market_duration: global_state.max_market_duration,
+        paused: global_state.paused,
+        min_oracle_reputation: global_state.min_oracle_reputation,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PlatformUpdatedEvent {
+    pub authority: Pubkey,
+    pub platform_fee_rate: u16,
+    pub oracle_fee_rate: u16,
+    pub min_market_duration: i64,
+    pub max_market_duration: i64,
+    pub paused: bool,
+    pub min_oracle_reputation: u32,
+    pub timestamp: i64,
+}
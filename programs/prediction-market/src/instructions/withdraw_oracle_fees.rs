@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::invariant::assert_fee_account_solvent;
+use super::withdraw_creator_fees::{FeeType, FeesWithdrawnEvent};
+
+#[derive(Accounts)]
+pub struct WithdrawOracleFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Resolved @ PredictionMarketError::MarketNotResolved,
+        constraint = market.oracle == oracle.key() @ PredictionMarketError::UnauthorizedOracle
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_fee", market.key().as_ref()],
+        bump,
+        token::mint = market.token_mint,
+        token::authority = protocol_fee_authority
+    )]
+    pub protocol_fee_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA used as the protocol fee authority
+    #[account(
+        seeds = [b"protocol_fee_authority"],
+        bump
+    )]
+    pub protocol_fee_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = destination.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub oracle: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<WithdrawOracleFees>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let amount = market.oracle_fees_accrued;
+
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+
+    let seeds = &[
+        b"protocol_fee_authority".as_ref(),
+        &[ctx.bumps.protocol_fee_authority],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.protocol_fee_account.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.protocol_fee_authority.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    market.oracle_fees_accrued = 0;
+
+    emit!(FeesWithdrawnEvent {
+        market: market.key(),
+        recipient: ctx.accounts.oracle.key(),
+        destination: ctx.accounts.destination.key(),
+        fee_type: FeeType::Oracle,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    assert_fee_account_solvent(&ctx.accounts.market, &mut ctx.accounts.protocol_fee_account)?;
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::invariant::assert_fee_account_solvent;
+use super::withdraw_creator_fees::{FeeType, FeesWithdrawnEvent};
+
+#[derive(Accounts)]
+pub struct WithdrawPlatformFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ PredictionMarketError::AdminPrivilegesRequired
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump
+    )]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_fee", market.key().as_ref()],
+        bump,
+        token::mint = market.token_mint,
+        token::authority = protocol_fee_authority
+    )]
+    pub protocol_fee_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA used as the protocol fee authority
+    #[account(
+        seeds = [b"protocol_fee_authority"],
+        bump
+    )]
+    pub protocol_fee_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = treasury.mint == market.token_mint @ PredictionMarketError::InvalidTokenMint
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<WithdrawPlatformFees>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+
+    // Oracle fees on a cancelled market were never earned and go to the platform
+    let mut amount = market.platform_fees_accrued;
+    let forfeited_oracle_fees = if market.status == MarketStatus::Cancelled {
+        market.oracle_fees_accrued
+    } else {
+        0
+    };
+    amount = amount
+        .checked_add(forfeited_oracle_fees)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+
+    let seeds = &[
+        b"protocol_fee_authority".as_ref(),
+        &[ctx.bumps.protocol_fee_authority],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.protocol_fee_account.to_account_info(),
+            to: ctx.accounts.treasury.to_account_info(),
+            authority: ctx.accounts.protocol_fee_authority.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    market.platform_fees_accrued = 0;
+    market.oracle_fees_accrued -= forfeited_oracle_fees;
+
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.total_fees_collected = global_state.total_fees_collected
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(FeesWithdrawnEvent {
+        market: market.key(),
+        recipient: ctx.accounts.authority.key(),
+        destination: ctx.accounts.treasury.key(),
+        fee_type: FeeType::Platform,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    assert_fee_account_solvent(&ctx.accounts.market, &mut ctx.accounts.protocol_fee_account)?;
+
+    Ok(())
+}
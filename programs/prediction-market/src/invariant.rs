@@ -0,0 +1,192 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::errors::PredictionMarketError;
+use crate::math::{apply_bps, mul_div_u64, Rounding};
+use crate::state::{LiquidityPool, Market, MarketStatus, OutcomeTokens};
+
+/// Collateral the market vault still owes, split by the product that owes it.
+///
+/// Every component is a lower bound on what claimants can still withdraw, so
+/// a vault below the total is insolvent rather than merely short of dust.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct MarketLiabilities {
+    pub parimutuel: u64,
+    pub positions: u64,
+    pub outcome_tokens: u64,
+}
+
+impl MarketLiabilities {
+    pub fn total(&self) -> Result<u64> {
+        self.parimutuel
+            .checked_add(self.positions)
+            .and_then(|v| v.checked_add(self.outcome_tokens))
+            .ok_or(PredictionMarketError::ArithmeticOverflow.into())
+    }
+}
+
+/// Outstanding market vault liabilities. Complete-set liabilities are only
+/// counted when the caller has the market's `OutcomeTokens` account.
+pub fn market_liabilities(market: &Market, outcome_tokens: Option<&OutcomeTokens>) -> Result<MarketLiabilities> {
+    Ok(MarketLiabilities {
+        parimutuel: parimutuel_liability(market)?,
+        positions: position_liability(market)?,
+        outcome_tokens: outcome_tokens
+            .map(|tokens| outcome_token_liability(market, tokens))
+            .transpose()?
+            .unwrap_or(0),
+    })
+}
+
+/// Stakes refundable while the market is open or cancelled; once resolved,
+/// the net pool owed to winning (or, if Invalid, all) stakes
+fn parimutuel_liability(market: &Market) -> Result<u64> {
+    let total_pool: u64 = market.outcomes.iter().map(|o| o.total_amount).sum();
+
+    let owed = match market.status {
+        MarketStatus::Resolved if market.resolved_invalid => {
+            mul_div_u64(total_pool, market.payout_ratio, 10000, Rounding::Down)?
+        }
+        MarketStatus::Resolved => match market.winning_outcome {
+            Some(outcome) => {
+                let winning_pool = market.outcomes[outcome as usize].total_amount;
+                mul_div_u64(winning_pool, market.payout_ratio, 10000, Rounding::Down)?
+            }
+            None => 0,
+        },
+        _ => total_pool,
+    };
+
+    Ok(owed.saturating_sub(market.bets_paid))
+}
+
+/// LMSR shares: the worst-case payout max_i q_i while trading, the payout of
+/// every unclaimed share once resolved or cancelled. Claims retire their shares
+/// from `total_shares`; a pro rata refund never pays less than the equal
+/// split, since the LMSR collateral covers max_i q_i.
+fn position_liability(market: &Market) -> Result<u64> {
+    if market.lmsr_b == 0 {
+        return Ok(0);
+    }
+
+    if !market.is_settled() {
+        return Ok(market.outcomes.iter().map(|o| o.total_shares).max().unwrap_or(0));
+    }
+
+    let mut owed = 0u64;
+    for (index, outcome) in market.outcomes.iter().enumerate() {
+        owed = owed
+            .checked_add(apply_bps(outcome.total_shares, market.payout_bps(index as u8), Rounding::Down)?)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    }
+
+    Ok(owed)
+}
+
+/// Complete sets: one unit per YES/NO pair while trading, each token's
+/// payout once resolved or cancelled
+fn outcome_token_liability(market: &Market, outcome_tokens: &OutcomeTokens) -> Result<u64> {
+    if !market.is_settled() {
+        return Ok(outcome_tokens.yes_token_supply.max(outcome_tokens.no_token_supply));
+    }
+
+    apply_bps(outcome_tokens.yes_token_supply, market.payout_bps(0), Rounding::Down)?
+        .checked_add(apply_bps(outcome_tokens.no_token_supply, market.payout_bps(1), Rounding::Down)?)
+        .ok_or(PredictionMarketError::ArithmeticOverflow.into())
+}
+
+/// Abort with `DataCorruption` unless the market vault covers its liabilities.
+/// Reloads the vault, so call it after the instruction's transfers.
+pub fn assert_market_vault_solvent(
+    market: &Market,
+    outcome_tokens: Option<&OutcomeTokens>,
+    market_vault: &mut Account<TokenAccount>,
+) -> Result<()> {
+    market_vault.reload()?;
+    let liabilities = market_liabilities(market, outcome_tokens)?.total()?;
+
+    if market_vault.amount < liabilities {
+        msg!(
+            "Market vault insolvent: balance {} below liabilities {}",
+            market_vault.amount,
+            liabilities
+        );
+        return Err(PredictionMarketError::DataCorruption.into());
+    }
+
+    Ok(())
+}
+
+/// Collateral the liquidity vault owes: AMM shares held by traders, unclaimed
+/// LP fees and, once the pool is settled, the settlement value still owed to LPs
+pub fn pool_liabilities(market: &Market, liquidity_pool: &LiquidityPool) -> Result<u64> {
+    let settlement = if liquidity_pool.settled { liquidity_pool.settlement_value } else { 0 };
+
+    amm_share_liability(market, liquidity_pool)?
+        .checked_add(liquidity_pool.unclaimed_fees)
+        .and_then(|v| v.checked_add(settlement))
+        .ok_or(PredictionMarketError::ArithmeticOverflow.into())
+}
+
+/// AMM shares: one unit per YES/NO pair while trading, each share's payout
+/// once resolved or cancelled
+pub fn amm_share_liability(market: &Market, liquidity_pool: &LiquidityPool) -> Result<u64> {
+    if !market.is_settled() {
+        return Ok(liquidity_pool.yes_shares_outstanding.max(liquidity_pool.no_shares_outstanding));
+    }
+
+    apply_bps(liquidity_pool.yes_shares_outstanding, market.payout_bps(0), Rounding::Down)?
+        .checked_add(apply_bps(liquidity_pool.no_shares_outstanding, market.payout_bps(1), Rounding::Down)?)
+        .ok_or(PredictionMarketError::ArithmeticOverflow.into())
+}
+
+/// Abort with `DataCorruption` unless the liquidity vault covers what it owes
+/// traders and LPs. Reloads the vault, so call it after the instruction's transfers.
+pub fn assert_pool_vault_solvent(
+    market: &Market,
+    liquidity_pool: &LiquidityPool,
+    liquidity_vault: &mut Account<TokenAccount>,
+) -> Result<()> {
+    liquidity_vault.reload()?;
+    let liabilities = pool_liabilities(market, liquidity_pool)?;
+
+    if liquidity_vault.amount < liabilities {
+        msg!(
+            "Liquidity vault insolvent: balance {} below liabilities {}",
+            liquidity_vault.amount,
+            liabilities
+        );
+        return Err(PredictionMarketError::DataCorruption.into());
+    }
+
+    Ok(())
+}
+
+/// Fees accrued to the creator, platform and oracle but not yet withdrawn
+pub fn accrued_fees(market: &Market) -> Result<u64> {
+    market.creator_fees_accrued
+        .checked_add(market.platform_fees_accrued)
+        .and_then(|v| v.checked_add(market.oracle_fees_accrued))
+        .ok_or(PredictionMarketError::ArithmeticOverflow.into())
+}
+
+/// Abort with `DataCorruption` unless the protocol fee account holds every
+/// accrued, unwithdrawn fee. Reloads the account, so call it after transfers.
+pub fn assert_fee_account_solvent(
+    market: &Market,
+    protocol_fee_account: &mut Account<TokenAccount>,
+) -> Result<()> {
+    protocol_fee_account.reload()?;
+    let fees = accrued_fees(market)?;
+
+    if protocol_fee_account.amount < fees {
+        msg!(
+            "Protocol fee account insolvent: balance {} below accrued fees {}",
+            protocol_fee_account.amount,
+            fees
+        );
+        return Err(PredictionMarketError::DataCorruption.into());
+    }
+
+    Ok(())
+}
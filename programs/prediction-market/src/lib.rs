@@ -1,6 +1,18 @@
-```rust
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+pub mod cpmm;
+pub mod errors;
+pub mod instructions;
+pub mod invariant;
+pub mod lmsr;
+pub mod math;
+pub mod pyth;
+pub mod state;
+pub mod switchboard;
+pub mod utils;
+
+pub use instructions::*;
+use state::{MarketType, PriceFeed, TradeType};
 
 declare_id!("PredMktProgram11111111111111111111111111111");
 
@@ -8,427 +20,355 @@ declare_id!("PredMktProgram11111111111111111111111111111");
 pub mod prediction_market {
     use super::*;
 
-    pub fn initialize_market(
-        ctx: Context<InitializeMarket>,
-        market_id: u64,
+    pub fn initialize_platform(
+        ctx: Context<InitializePlatform>,
+        platform_fee_rate: u16,
+        oracle_fee_rate: u16,
+        min_market_duration: i64,
+        max_market_duration: i64,
+    ) -> Result<()> {
+        instructions::initialize_platform::handler(
+            ctx,
+            platform_fee_rate,
+            oracle_fee_rate,
+            min_market_duration,
+            max_market_duration,
+        )
+    }
+
+    pub fn update_platform(
+        ctx: Context<UpdatePlatform>,
+        platform_fee_rate: Option<u16>,
+        oracle_fee_rate: Option<u16>,
+        min_market_duration: Option<i64>,
+        max_market_duration: Option<i64>,
+        paused: Option<bool>,
+        min_oracle_reputation: Option<u32>,
+    ) -> Result<()> {
+        instructions::update_platform::handler(
+            ctx,
+            platform_fee_rate,
+            oracle_fee_rate,
+            min_market_duration,
+            max_market_duration,
+            paused,
+            min_oracle_reputation,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_market(
+        ctx: Context<CreateMarket>,
+        market_id: String,
         title: String,
         description: String,
         category: String,
+        outcome_titles: Vec<String>,
+        end_time: i64,
         resolution_time: i64,
-        oracle_pubkey: Pubkey,
+        resolution_source: String,
+        oracle: Pubkey,
         min_bet_amount: u64,
+        max_bet_amount: u64,
+        initial_liquidity: u64,
+        creator_fee_bps: u16,
+        cancellation_window: i64,
+        cancellation_fee_bps: u16,
+        cancellation_lock_time: i64,
+        lmsr_liquidity: u64,
+        market_type: MarketType,
+        lower_bound: i64,
+        upper_bound: i64,
+        price_feed: Option<PriceFeed>,
+        resolution_bond: u64,
+        dispute_window: i64,
     ) -> Result<()> {
-        let market = &mut ctx.accounts.market;
-        let clock = Clock::get()?;
-
-        require!(resolution_time > clock.unix_timestamp, ErrorCode::InvalidResolutionTime);
-        require!(title.len() <= 100, ErrorCode::TitleTooLong);
-        require!(description.len() <= 500, ErrorCode::DescriptionTooLong);
-        require!(min_bet_amount > 0, ErrorCode::InvalidMinBetAmount);
-
-        market.market_id = market_id;
-        market.title = title;
-        market.description = description;
-        market.category = category;
-        market.creator = ctx.accounts.creator.key();
-        market.oracle = oracle_pubkey;
-        market.resolution_time = resolution_time;
-        market.creation_time = clock.unix_timestamp;
-        market.status = MarketStatus::Active;
-        market.total_yes_amount = 0;
-        market.total_no_amount = 0;
-        market.total_volume = 0;
-        market.min_bet_amount = min_bet_amount;
-        market.resolved_outcome = None;
-        market.resolution_timestamp = None;
-        market.bump = ctx.bumps.market;
-
-        emit!(MarketCreated {
+        instructions::create_market::handler(
+            ctx,
             market_id,
-            creator: ctx.accounts.creator.key(),
-            title: market.title.clone(),
+            title,
+            description,
+            category,
+            outcome_titles,
+            end_time,
             resolution_time,
-        });
+            resolution_source,
+            oracle,
+            min_bet_amount,
+            max_bet_amount,
+            initial_liquidity,
+            creator_fee_bps,
+            cancellation_window,
+            cancellation_fee_bps,
+            cancellation_lock_time,
+            lmsr_liquidity,
+            market_type,
+            lower_bound,
+            upper_bound,
+            price_feed,
+            resolution_bond,
+            dispute_window,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_market(
+        ctx: Context<UpdateMarket>,
+        title: Option<String>,
+        description: Option<String>,
+        category: Option<String>,
+        resolution_source: Option<String>,
+        min_bet_amount: Option<u64>,
+        max_bet_amount: Option<u64>,
+        end_time: Option<i64>,
+        sweep_destination: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::update_market::handler(
+            ctx,
+            title,
+            description,
+            category,
+            resolution_source,
+            min_bet_amount,
+            max_bet_amount,
+            end_time,
+            sweep_destination,
+        )
+    }
 
-        Ok(())
+    pub fn place_bet(ctx: Context<PlaceBet>, outcome: u8, amount: u64) -> Result<()> {
+        instructions::place_bet::handler(ctx, outcome, amount)
     }
 
-    pub fn place_bet(
-        ctx: Context<PlaceBet>,
-        market_id: u64,
-        outcome: bool,
+    pub fn cancel_bet(ctx: Context<CancelBet>, outcome: u8, amount: u64) -> Result<()> {
+        instructions::cancel_bet::handler(ctx, outcome, amount)
+    }
+
+    pub fn buy_shares(
+        ctx: Context<BuyShares>,
+        outcome: u8,
+        shares: u64,
+        max_cost: u64,
+    ) -> Result<()> {
+        instructions::buy_shares::handler(ctx, outcome, shares, max_cost)
+    }
+
+    pub fn sell_shares(
+        ctx: Context<SellShares>,
+        outcome: u8,
+        shares: u64,
+        min_proceeds: u64,
+    ) -> Result<()> {
+        instructions::sell_shares::handler(ctx, outcome, shares, min_proceeds)
+    }
+
+    pub fn buy_outcome(
+        ctx: Context<BuyOutcome>,
+        outcome: u8,
         amount: u64,
+        min_shares_out: u64,
     ) -> Result<()> {
-        let market = &mut ctx.accounts.market;
-        let bet = &mut ctx.accounts.bet;
-        let clock = Clock::get()?;
-
-        require!(market.status == MarketStatus::Active, ErrorCode::MarketNotActive);
-        require!(clock.unix_timestamp < market.resolution_time, ErrorCode::MarketExpired);
-        require!(amount >= market.min_bet_amount, ErrorCode::BetAmountTooLow);
-
-        // Transfer tokens from bettor to market vault
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.bettor_token_account.to_account_info(),
-            to: ctx.accounts.market_vault.to_account_info(),
-            authority: ctx.accounts.bettor.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, amount)?;
-
-        // Update market totals
-        if outcome {
-            market.total_yes_amount = market.total_yes_amount.checked_add(amount).ok_or(ErrorCode::Overflow)?;
-        } else {
-            market.total_no_amount = market.total_no_amount.checked_add(amount).ok_or(ErrorCode::Overflow)?;
-        }
-        market.total_volume = market.total_volume.checked_add(amount).ok_or(ErrorCode::Overflow)?;
-
-        // Initialize bet account
-        bet.market = market.key();
-        bet.bettor = ctx.accounts.bettor.key();
-        bet.outcome = outcome;
-        bet.amount = amount;
-        bet.timestamp = clock.unix_timestamp;
-        bet.claimed = false;
-        bet.bump = ctx.bumps.bet;
-
-        emit!(BetPlaced {
-            market_id,
-            bettor: ctx.accounts.bettor.key(),
-            outcome,
-            amount,
-            timestamp: clock.unix_timestamp,
-        });
+        instructions::buy_outcome::handler(ctx, outcome, amount, min_shares_out)
+    }
 
-        Ok(())
+    pub fn sell_outcome(
+        ctx: Context<SellOutcome>,
+        outcome: u8,
+        return_amount: u64,
+        max_cost: u64,
+    ) -> Result<()> {
+        instructions::sell_outcome::handler(ctx, outcome, return_amount, max_cost)
     }
 
     pub fn resolve_market(
         ctx: Context<ResolveMarket>,
-        market_id: u64,
-        outcome: bool,
+        outcome: u8,
+        oracle_data: Vec<u8>,
     ) -> Result<()> {
-        let market = &mut ctx.accounts.market;
-        let clock = Clock::get()?;
+        instructions::resolve_market::handler(ctx, outcome, oracle_data)
+    }
 
-        require!(market.status == MarketStatus::Active, ErrorCode::MarketNotActive);
-        require!(ctx.accounts.oracle.key() == market.oracle, ErrorCode::UnauthorizedOracle);
-        require!(clock.unix_timestamp >= market.resolution_time, ErrorCode::MarketNotExpired);
+    pub fn resolve_scalar_market(
+        ctx: Context<ResolveScalarMarket>,
+        value: i64,
+        oracle_data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::resolve_scalar_market::handler(ctx, value, oracle_data)
+    }
+
+    pub fn resolve_from_pyth(ctx: Context<ResolveFromPyth>) -> Result<()> {
+        instructions::resolve_from_pyth::handler(ctx)
+    }
 
-        market.status = MarketStatus::Resolved;
-        market.resolved_outcome = Some(outcome);
-        market.resolution_timestamp = Some(clock.unix_timestamp);
+    pub fn resolve_from_switchboard(ctx: Context<ResolveFromSwitchboard>) -> Result<()> {
+        instructions::resolve_from_switchboard::handler(ctx)
+    }
 
-        emit!(MarketResolved {
-            market_id,
-            outcome,
-            resolution_timestamp: clock.unix_timestamp,
-        });
+    pub fn propose_resolution(ctx: Context<ProposeResolution>, outcome: u8) -> Result<()> {
+        instructions::propose_resolution::handler(ctx, outcome)
+    }
+
+    pub fn dispute_resolution(ctx: Context<DisputeResolution>, outcome: u8) -> Result<()> {
+        instructions::dispute_resolution::handler(ctx, outcome)
+    }
 
-        Ok(())
+    pub fn finalize_resolution(ctx: Context<FinalizeResolution>, outcome: u8) -> Result<()> {
+        instructions::finalize_resolution::handler(ctx, outcome)
     }
 
-    pub fn claim_winnings(
-        ctx: Context<ClaimWinnings>,
-        market_id: u64,
+    pub fn configure_oracle_committee<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ConfigureOracleCommittee<'info>>,
+        threshold: u8,
+        vote_deadline: i64,
     ) -> Result<()> {
-        let market = &ctx.accounts.market;
-        let bet = &mut ctx.accounts.bet;
-
-        require!(market.status == MarketStatus::Resolved, ErrorCode::MarketNotResolved);
-        require!(!bet.claimed, ErrorCode::AlreadyClaimed);
-        require!(bet.bettor == ctx.accounts.bettor.key(), ErrorCode::UnauthorizedClaimer);
-
-        let resolved_outcome = market.resolved_outcome.ok_or(ErrorCode::MarketNotResolved)?;
-        require!(bet.outcome == resolved_outcome, ErrorCode::LosingBet);
-
-        // Calculate winnings
-        let total_winning_amount = if resolved_outcome {
-            market.total_yes_amount
-        } else {
-            market.total_no_amount
-        };
-
-        let total_pool = market.total_yes_amount.checked_add(market.total_no_amount).ok_or(ErrorCode::Overflow)?;
-        let winnings = (bet.amount as u128)
-            .checked_mul(total_pool as u128)
-            .ok_or(ErrorCode::Overflow)?
-            .checked_div(total_winning_amount as u128)
-            .ok_or(ErrorCode::DivisionByZero)?
-            as u64;
-
-        // Transfer winnings from market vault to bettor
-        let seeds = &[
-            b"market",
-            &market.market_id.to_le_bytes(),
-            &[market.bump],
-        ];
-        let signer = &[&seeds[..]];
-
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.market_vault.to_account_info(),
-            to: ctx.accounts.bettor_token_account.to_account_info(),
-            authority: market.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, winnings)?;
-
-        bet.claimed = true;
-
-        emit!(WinningsClaimed {
-            market_id,
-            bettor: ctx.accounts.bettor.key(),
-            amount: winnings,
-        });
+        instructions::configure_oracle_committee::handler(ctx, threshold, vote_deadline)
+    }
 
-        Ok(())
+    pub fn submit_oracle_vote(ctx: Context<SubmitOracleVote>, outcome: u8) -> Result<()> {
+        instructions::submit_oracle_vote::handler(ctx, outcome)
     }
 
-    pub fn cancel_market(
-        ctx: Context<CancelMarket>,
-        market_id: u64,
+    pub fn resolve_by_quorum(ctx: Context<ResolveByQuorum>) -> Result<()> {
+        instructions::resolve_by_quorum::handler(ctx)
+    }
+
+    pub fn register_oracle(
+        ctx: Context<RegisterOracle>,
+        name: String,
+        description: String,
     ) -> Result<()> {
-        let market = &mut ctx.accounts.market;
-        let clock = Clock::get()?;
+        instructions::register_oracle::handler(ctx, name, description)
+    }
 
-        require!(market.status == MarketStatus::Active, ErrorCode::MarketNotActive);
-        require!(
-            ctx.accounts.authority.key() == market.creator || 
-            ctx.accounts.authority.key() == market.oracle,
-            ErrorCode::UnauthorizedCancel
-        );
-        require!(clock.unix_timestamp < market.resolution_time, ErrorCode::MarketExpired);
+    pub fn deactivate_oracle(ctx: Context<DeactivateOracle>) -> Result<()> {
+        instructions::deactivate_oracle::handler(ctx)
+    }
 
-        market.status = MarketStatus::Cancelled;
+    pub fn settle_oracle_vote(ctx: Context<SettleOracleVote>) -> Result<()> {
+        instructions::settle_oracle_vote::handler(ctx)
+    }
 
-        emit!(MarketCancelled {
-            market_id,
-            cancelled_by: ctx.accounts.authority.key(),
-        });
+    pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
+        instructions::claim_winnings::handler(ctx)
+    }
+
+    pub fn claim_position(ctx: Context<ClaimPosition>) -> Result<()> {
+        instructions::claim_position::handler(ctx)
+    }
+
+    pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+        instructions::close_position::handler(ctx)
+    }
 
-        Ok(())
+    pub fn claim_outcome(ctx: Context<ClaimOutcome>) -> Result<()> {
+        instructions::claim_outcome::handler(ctx)
     }
 
-    pub fn refund_bet(
-        ctx: Context<RefundBet>,
-        market_id: u64,
+    pub fn close_amm_position(ctx: Context<CloseAmmPosition>) -> Result<()> {
+        instructions::close_amm_position::handler(ctx)
+    }
+
+    pub fn mint_complete_set(ctx: Context<MintCompleteSet>, amount: u64) -> Result<()> {
+        instructions::mint_complete_set::handler(ctx, amount)
+    }
+
+    pub fn redeem_complete_set(ctx: Context<RedeemCompleteSet>, amount: u64) -> Result<()> {
+        instructions::redeem_complete_set::handler(ctx, amount)
+    }
+
+    pub fn redeem_winning_tokens(ctx: Context<RedeemWinningTokens>, amount: u64) -> Result<()> {
+        instructions::redeem_winning_tokens::handler(ctx, amount)
+    }
+
+    pub fn initialize_order_book(ctx: Context<InitializeOrderBook>, outcome: u8) -> Result<()> {
+        instructions::initialize_order_book::handler(ctx, outcome)
+    }
+
+    pub fn place_limit_order<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PlaceLimitOrder<'info>>,
+        outcome: u8,
+        side: TradeType,
+        price: u64,
+        quantity: u64,
     ) -> Result<()> {
-        let market = &ctx.accounts.market;
-        let bet = &mut ctx.accounts.bet;
-
-        require!(market.status == MarketStatus::Cancelled, ErrorCode::MarketNotCancelled);
-        require!(!bet.claimed, ErrorCode::AlreadyClaimed);
-        require!(bet.bettor == ctx.accounts.bettor.key(), ErrorCode::UnauthorizedClaimer);
-
-        // Transfer bet amount back to bettor
-        let seeds = &[
-            b"market",
-            &market.market_id.to_le_bytes(),
-            &[market.bump],
-        ];
-        let signer = &[&seeds[..]];
-
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.market_vault.to_account_info(),
-            to: ctx.accounts.bettor_token_account.to_account_info(),
-            authority: market.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, bet.amount)?;
-
-        bet.claimed = true;
-
-        emit!(BetRefunded {
-            market_id,
-            bettor: ctx.accounts.bettor.key(),
-            amount: bet.amount,
-        });
+        instructions::place_limit_order::handler(ctx, outcome, side, price, quantity)
+    }
 
-        Ok(())
+    pub fn cancel_order(ctx: Context<CancelOrder>, outcome: u8, order_id: u64) -> Result<()> {
+        instructions::cancel_order::handler(ctx, outcome, order_id)
     }
-}
 
-#[derive(Accounts)]
-#[instruction(market_id: u64)]
-pub struct InitializeMarket<'info> {
-    #[account(
-        init,
-        payer = creator,
-        space = Market::LEN,
-        seeds = [b"market", &market_id.to_le_bytes()],
-        bump
-    )]
-    pub market: Account<'info, Market>,
-    
-    #[account(
-        init,
-        payer = creator,
-        token::mint = mint,
-        token::authority = market,
-        seeds = [b"vault", &market_id.to_le_bytes()],
-        bump
-    )]
-    pub market_vault: Account<'info, TokenAccount>,
-    
-    pub mint: Account<'info, anchor_spl::token::Mint>,
-    
-    #[account(mut)]
-    pub creator: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub rent: Sysvar<'info, Rent>,
-}
+    pub fn cancel_market(ctx: Context<CancelMarket>) -> Result<()> {
+        instructions::cancel_market::handler(ctx)
+    }
 
-#[derive(Accounts)]
-#[instruction(market_id: u64)]
-pub struct PlaceBet<'info> {
-    #[account(
-        mut,
-        seeds = [b"market", &market_id.to_le_bytes()],
-        bump = market.bump
-    )]
-    pub market: Account<'info, Market>,
-    
-    #[account(
-        init,
-        payer = bettor,
-        space = Bet::LEN,
-        seeds = [b"bet", market.key().as_ref(), bettor.key().as_ref()],
-        bump
-    )]
-    pub bet: Account<'info, Bet>,
-    
-    #[account(
-        mut,
-        seeds = [b"vault", &market_id.to_le_bytes()],
-        bump
-    )]
-    pub market_vault: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub bettor_token_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub bettor: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-}
+    pub fn expire_market(ctx: Context<ExpireMarket>) -> Result<()> {
+        instructions::expire_market::handler(ctx)
+    }
 
-#[derive(Accounts)]
-#[instruction(market_id: u64)]
-pub struct ResolveMarket<'info> {
-    #[account(
-        mut,
-        seeds = [b"market", &market_id.to_le_bytes()],
-        bump = market.bump
-    )]
-    pub market: Account<'info, Market>,
-    
-    pub oracle: Signer<'info>,
-}
+    pub fn refund_bet(ctx: Context<RefundBet>) -> Result<()> {
+        instructions::refund_bet::handler(ctx)
+    }
 
-#[derive(Accounts)]
-#[instruction(market_id: u64)]
-pub struct ClaimWinnings<'info> {
-    #[account(
-        seeds = [b"market", &market_id.to_le_bytes()],
-        bump = market.bump
-    )]
-    pub market: Account<'info, Market>,
-    
-    #[account(
-        mut,
-        seeds = [b"bet", market.key().as_ref(), bettor.key().as_ref()],
-        bump = bet.bump
-    )]
-    pub bet: Account<'info, Bet>,
-    
-    #[account(
-        mut,
-        seeds = [b"vault", &market_id.to_le_bytes()],
-        bump
-    )]
-    pub market_vault: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub bettor_token_account: Account<'info, TokenAccount>,
-    
-    pub bettor: Signer<'info>,
-    
-    pub token_program: Program<'info, Token>,
-}
+    pub fn sweep_market(ctx: Context<SweepMarket>) -> Result<()> {
+        instructions::sweep_market::handler(ctx)
+    }
 
-#[derive(Accounts)]
-#[instruction(market_id: u64)]
-pub struct CancelMarket<'info> {
-    #[account(
-        mut,
-        seeds = [b"market", &market_id.to_le_bytes()],
-        bump = market.bump
-    )]
-    pub market: Account<'info, Market>,
-    
-    pub authority: Signer<'info>,
-}
+    pub fn sweep_order_book(ctx: Context<SweepOrderBook>) -> Result<()> {
+        instructions::sweep_order_book::handler(ctx)
+    }
 
-#[derive(Accounts)]
-#[instruction(market_id: u64)]
-pub struct RefundBet<'info> {
-    #[account(
-        seeds = [b"market", &market_id.to_le_bytes()],
-        bump = market.bump
-    )]
-    pub market: Account<'info, Market>,
-    
-    #[account(
-        mut,
-        seeds = [b"bet", market.key().as_ref(), bettor.key().as_ref()],
-        bump = bet.bump
-    )]
-    pub bet: Account<'info, Bet>,
-    
-    #[account(
-        mut,
-        seeds = [b"vault", &market_id.to_le_bytes()],
-        bump
-    )]
-    pub market_vault: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub bettor_token_account: Account<'info, TokenAccount>,
-    
-    pub bettor: Signer<'info>,
-    
-    pub token_program: Program<'info, Token>,
-}
+    pub fn withdraw_creator_fees(ctx: Context<WithdrawCreatorFees>) -> Result<()> {
+        instructions::withdraw_creator_fees::handler(ctx)
+    }
 
-#[account]
-pub struct Market {
-    pub market_id: u64,
-    pub title: String,
-    pub description: String,
-    pub category: String,
-    pub creator: Pubkey,
-    pub oracle: Pubkey,
-    pub resolution_time: i64,
-    pub creation_time: i64,
-    pub status: MarketStatus,
-    pub total_yes_amount: u64,
-    pub total_no_amount: u64,
-    pub total_volume: u64,
-    pub min_bet_amount: u64,
-    pub resolved_outcome: Option<bool>,
-    pub resolution_timestamp: Option<i64>,
-    pub bump: u8,
-}
+    pub fn withdraw_platform_fees(ctx: Context<WithdrawPlatformFees>) -> Result<()> {
+        instructions::withdraw_platform_fees::handler(ctx)
+    }
+
+    pub fn withdraw_oracle_fees(ctx: Context<WithdrawOracleFees>) -> Result<()> {
+        instructions::withdraw_oracle_fees::handler(ctx)
+    }
 
-impl Market {
-    pub const LEN: usize = 8 + // discriminator
-        8 + // market_id
-        4 + 100 + // title (String)
\ No newline at end of file
+    pub fn add_liquidity(
+        ctx: Context<AddLiquidity>,
+        outcome_a_amount: u64,
+        outcome_b_amount: u64,
+        min_lp_tokens: u64,
+    ) -> Result<()> {
+        instructions::add_liquidity::handler(
+            ctx,
+            outcome_a_amount,
+            outcome_b_amount,
+            min_lp_tokens,
+        )
+    }
+
+    pub fn add_liquidity_collateral(
+        ctx: Context<AddLiquidityCollateral>,
+        amount: u64,
+        min_lp_tokens: u64,
+    ) -> Result<()> {
+        instructions::add_liquidity_collateral::handler(ctx, amount, min_lp_tokens)
+    }
+
+    pub fn remove_liquidity(ctx: Context<RemoveLiquidity>, lp_tokens_to_burn: u64) -> Result<()> {
+        instructions::remove_liquidity::handler(ctx, lp_tokens_to_burn)
+    }
+
+    pub fn claim_lp_fees(ctx: Context<ClaimLpFees>) -> Result<()> {
+        instructions::claim_lp_fees::handler(ctx)
+    }
+
+    pub fn settle_pool(ctx: Context<SettlePool>) -> Result<()> {
+        instructions::settle_pool::handler(ctx)
+    }
+
+    pub fn settle_liquidity(ctx: Context<SettleLiquidity>, lp_tokens_to_burn: u64) -> Result<()> {
+        instructions::settle_liquidity::handler(ctx, lp_tokens_to_burn)
+    }
+
+    pub fn audit_market(ctx: Context<AuditMarket>) -> Result<MarketAudit> {
+        instructions::audit_market::handler(ctx)
+    }
+}
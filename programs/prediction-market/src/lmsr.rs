@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::PredictionMarketError;
+use crate::math::{exp_neg, ln, to_units, Rounding, SCALE};
+
+/// Unnormalised outcome weights exp((q_i - max_q) / b) and their sum
+fn weights(quantities: &[u64], b: u64) -> Result<(Vec<u128>, u128)> {
+    require!(b > 0, PredictionMarketError::NoLiquidity);
+    let max_q = quantities.iter().copied().max().ok_or(PredictionMarketError::InvalidOutcomeCount)?;
+
+    let weights: Vec<u128> = quantities
+        .iter()
+        .map(|q| exp_neg((max_q - q) as u128 * SCALE / b as u128))
+        .collect();
+    let sum = weights.iter().sum();
+
+    Ok((weights, sum))
+}
+
+/// LMSR cost function C(q) = b * ln(sum_i exp(q_i / b)) at `SCALE` precision.
+///
+/// Evaluated as max_q + b * ln(sum_i exp((q_i - max_q) / b)) so every
+/// exponent is non-positive and the logarithm argument lies in [1, n].
+pub fn cost(quantities: &[u64], b: u64) -> Result<u128> {
+    let max_q = quantities.iter().copied().max().ok_or(PredictionMarketError::InvalidOutcomeCount)?;
+    let (_, sum) = weights(quantities, b)?;
+    let ln_sum = ln(sum)?;
+
+    (max_q as u128)
+        .checked_mul(SCALE)
+        .and_then(|v| v.checked_add((b as u128).checked_mul(ln_sum)?))
+        .ok_or(PredictionMarketError::ArithmeticOverflow.into())
+}
+
+/// Collateral required to buy `shares` of `outcome`, rounded up
+pub fn buy_cost(quantities: &[u64], b: u64, outcome: usize, shares: u64) -> Result<u64> {
+    let before = cost(quantities, b)?;
+
+    let mut after_quantities = quantities.to_vec();
+    let q = after_quantities.get_mut(outcome).ok_or(PredictionMarketError::InvalidOutcome)?;
+    *q = q.checked_add(shares).ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    let after = cost(&after_quantities, b)?;
+
+    let diff = after.checked_sub(before).ok_or(PredictionMarketError::InvalidOdds)?;
+
+    to_units(diff, Rounding::Up)
+}
+
+/// Collateral returned for selling `shares` of `outcome`, rounded down
+pub fn sell_proceeds(quantities: &[u64], b: u64, outcome: usize, shares: u64) -> Result<u64> {
+    let before = cost(quantities, b)?;
+
+    let mut after_quantities = quantities.to_vec();
+    let q = after_quantities.get_mut(outcome).ok_or(PredictionMarketError::InvalidOutcome)?;
+    *q = q.checked_sub(shares).ok_or(PredictionMarketError::InsufficientTokenBalance)?;
+    let after = cost(&after_quantities, b)?;
+
+    let diff = before.checked_sub(after).ok_or(PredictionMarketError::InvalidOdds)?;
+
+    to_units(diff, Rounding::Down)
+}
+
+/// Instantaneous price of every outcome in basis points
+pub fn prices(quantities: &[u64], b: u64) -> Result<Vec<u64>> {
+    let (weights, sum) = weights(quantities, b)?;
+
+    Ok(weights
+        .iter()
+        .map(|w| (w * 10000 / sum) as u64)
+        .collect())
+}
+
+/// Worst-case market maker loss b * ln(n), rounded up; this is the subsidy
+/// the creator deposits when opening an LMSR market
+pub fn max_loss(b: u64, outcome_count: usize) -> Result<u64> {
+    require!(outcome_count >= 2, PredictionMarketError::InvalidOutcomeCount);
+
+    let ln_n = ln(outcome_count as u128 * SCALE)?;
+    let loss = (b as u128)
+        .checked_mul(ln_n)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    to_units(loss, Rounding::Up)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const B: u64 = 1_000_000_000;
+
+    #[test]
+    fn buy_then_sell_round_trip_favors_market_maker() {
+        for &(ref quantities, outcome, shares) in &[
+            (vec![0u64, 0], 0usize, 500_000_000u64),
+            (vec![3_000_000_000, 1_000_000_000, 7], 2, 1_234_567),
+            (vec![10, 20, 30, 40], 1, 4 * B),
+        ] {
+            let paid = buy_cost(quantities, B, outcome, shares).unwrap();
+
+            let mut after = quantities.clone();
+            after[outcome] += shares;
+            let returned = sell_proceeds(&after, B, outcome, shares).unwrap();
+
+            // The same cost difference, rounded up to buy and down to sell
+            assert!(returned <= paid);
+            assert!(paid - returned <= 2);
+        }
+    }
+
+    #[test]
+    fn buy_cost_matches_closed_form() {
+        // Two outcomes from q = 0: b * (ln(e^(s/b) + 1) - ln 2)
+        let cost = buy_cost(&[0, 0], B, 0, B / 2).unwrap();
+        let expected = (B as f64 * ((0.5f64.exp() + 1.0).ln() - 2f64.ln())).ceil() as u64;
+        assert!(cost.abs_diff(expected) <= 1, "{} vs {}", cost, expected);
+    }
+
+    #[test]
+    fn prices_sum_to_one_and_follow_quantities() {
+        let prices = prices(&[B / 2, 0], B).unwrap();
+        let expected = 10000.0 * 0.5f64.exp() / (0.5f64.exp() + 1.0);
+        assert!((prices[0] as f64 - expected).abs() <= 1.0);
+
+        for quantities in [vec![0u64, 0, 0], vec![5 * B, 0, 3], vec![1, 2, 3, 4, 5]] {
+            let prices = super::prices(&quantities, B).unwrap();
+            let total: u64 = prices.iter().sum();
+            // Each price is floored, so the sum falls short by at most one per outcome
+            assert!(total <= 10000 && total > 10000 - quantities.len() as u64);
+        }
+    }
+
+    #[test]
+    fn price_is_marginal_cost() {
+        let quantities = [2 * B, B];
+        let price = prices(&quantities, B).unwrap()[0];
+        // Cost of a small buy per share, in basis points
+        let shares = B / 1000;
+        let marginal = buy_cost(&quantities, B, 0, shares).unwrap() as u128 * 10000 / shares as u128;
+        assert!((marginal as u64).abs_diff(price) <= 5, "{} vs {}", marginal, price);
+    }
+
+    #[test]
+    fn max_loss_is_b_ln_n() {
+        for n in 2..=10usize {
+            let expected = (B as f64 * (n as f64).ln()).ceil() as u64;
+            assert!(max_loss(B, n).unwrap().abs_diff(expected) <= 1);
+        }
+        assert!(max_loss(B, 1).is_err());
+    }
+}
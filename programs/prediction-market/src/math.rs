@@ -0,0 +1,351 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::PredictionMarketError;
+
+/// Fixed-point scale shared by the pricing engines (12 decimal places)
+pub const SCALE: u128 = 1_000_000_000_000;
+
+/// Beyond this exponent exp(-x) is below one unit of `SCALE`
+const MAX_EXP_INPUT: u128 = 40 * SCALE;
+
+/// Extra precision the exp and ln series are evaluated at, so truncating each
+/// term costs far less than one unit of `SCALE`
+const GUARD: u128 = 1_000_000;
+
+/// ln(2) at `SCALE * GUARD` precision
+const LN_2_GUARDED: u128 = 693_147_180_559_945_309;
+
+/// Direction to round a division. Callers pick whichever side favors the
+/// vault: payouts round down, amounts charged to users round up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+/// 192-bit unsigned integer, little-endian 64-bit limbs. Only used for the
+/// intermediate product in `mul_div`, where a * b may exceed u128.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct U192([u64; 3]);
+
+impl U192 {
+    pub fn from_u128(value: u128) -> Self {
+        U192([value as u64, (value >> 64) as u64, 0])
+    }
+
+    /// Full product of two u128 values, or None if it needs more than 192 bits
+    pub fn checked_mul(a: u128, b: u128) -> Option<Self> {
+        let a = [a as u64, (a >> 64) as u64];
+        let b = [b as u64, (b >> 64) as u64];
+        let mut limbs = [0u64; 4];
+
+        for (i, &a_limb) in a.iter().enumerate() {
+            let mut carry = 0u128;
+            for (j, &b_limb) in b.iter().enumerate() {
+                let acc = limbs[i + j] as u128 + a_limb as u128 * b_limb as u128 + carry;
+                limbs[i + j] = acc as u64;
+                carry = acc >> 64;
+            }
+            limbs[i + 2] = carry as u64;
+        }
+
+        if limbs[3] != 0 {
+            return None;
+        }
+        Some(U192([limbs[0], limbs[1], limbs[2]]))
+    }
+
+    /// Quotient and remainder of division by a non-zero u128, by binary long
+    /// division over the 192 bits
+    pub fn div_rem(self, divisor: u128) -> (Self, u128) {
+        let mut quotient = [0u64; 3];
+        let mut remainder = 0u128;
+
+        for bit in (0..192).rev() {
+            let next = (self.0[bit / 64] >> (bit % 64)) & 1;
+            let overflow = remainder >> 127 == 1;
+            remainder = (remainder << 1) | next as u128;
+            // A shifted-out top bit means the true remainder is >= 2^128 > divisor
+            if overflow || remainder >= divisor {
+                remainder = remainder.wrapping_sub(divisor);
+                quotient[bit / 64] |= 1 << (bit % 64);
+            }
+        }
+
+        (U192(quotient), remainder)
+    }
+
+    pub fn to_u128(self) -> Option<u128> {
+        if self.0[2] != 0 {
+            return None;
+        }
+        Some(self.0[0] as u128 | (self.0[1] as u128) << 64)
+    }
+}
+
+/// `a * b / denominator` with the given rounding. The product is carried in
+/// 192 bits, so only the quotient itself has to fit in u128.
+pub fn mul_div(a: u128, b: u128, denominator: u128, rounding: Rounding) -> Result<u128> {
+    require!(denominator > 0, PredictionMarketError::DivisionByZero);
+
+    let (quotient, remainder) = match a.checked_mul(b) {
+        Some(product) => (product / denominator, product % denominator),
+        None => {
+            let product = U192::checked_mul(a, b).ok_or(PredictionMarketError::ArithmeticOverflow)?;
+            let (quotient, remainder) = product.div_rem(denominator);
+            (
+                quotient.to_u128().ok_or(PredictionMarketError::ArithmeticOverflow)?,
+                remainder,
+            )
+        }
+    };
+
+    if rounding == Rounding::Up && remainder > 0 {
+        return quotient
+            .checked_add(1)
+            .ok_or(PredictionMarketError::ArithmeticOverflow.into());
+    }
+    Ok(quotient)
+}
+
+/// `mul_div` for token amounts; fails if the result does not fit in u64
+pub fn mul_div_u64(a: u64, b: u64, denominator: u64, rounding: Rounding) -> Result<u64> {
+    let value = mul_div(a as u128, b as u128, denominator as u128, rounding)?;
+    u64::try_from(value).map_err(|_| PredictionMarketError::ArithmeticOverflow.into())
+}
+
+/// `amount * bps / 10000` with the given rounding
+pub fn apply_bps(amount: u64, bps: u64, rounding: Rounding) -> Result<u64> {
+    mul_div_u64(amount, bps, 10000, rounding)
+}
+
+/// `numerator / denominator` with the given rounding
+pub fn div_round(numerator: u128, denominator: u128, rounding: Rounding) -> Result<u128> {
+    mul_div(numerator, 1, denominator, rounding)
+}
+
+/// Product of two `SCALE` fixed-point values
+pub fn fixed_mul(a: u128, b: u128, rounding: Rounding) -> Result<u128> {
+    mul_div(a, b, SCALE, rounding)
+}
+
+/// Quotient of two `SCALE` fixed-point values
+pub fn fixed_div(a: u128, b: u128, rounding: Rounding) -> Result<u128> {
+    mul_div(a, SCALE, b, rounding)
+}
+
+/// Convert a `SCALE` fixed-point value to whole token units
+pub fn to_units(value: u128, rounding: Rounding) -> Result<u64> {
+    let units = div_round(value, SCALE, rounding)?;
+    u64::try_from(units).map_err(|_| PredictionMarketError::ArithmeticOverflow.into())
+}
+
+/// floor(sqrt(n)), exact for every u128
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    // Newton's method from a power of two at or above the root decreases
+    // monotonically and stops at the floor
+    let bits = 128 - n.leading_zeros();
+    let mut x = 1u128 << bits.div_ceil(2);
+    loop {
+        let y = (x + n / x) >> 1;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// exp(-x) for a non-negative fixed-point `x`, returned at `SCALE` precision.
+///
+/// Uses exp(-x) = 2^-k * exp(-r) with r in [0, ln 2) and a Taylor series for
+/// exp(-r), evaluated with `GUARD` extra digits and rounded down; absolute
+/// error is below 1 unit of `SCALE`.
+pub fn exp_neg(x: u128) -> u128 {
+    if x > MAX_EXP_INPUT {
+        return 0;
+    }
+
+    let one = (SCALE * GUARD) as i128;
+    let x = x * GUARD;
+    let k = x / LN_2_GUARDED;
+    let r = (x - k * LN_2_GUARDED) as i128;
+
+    let mut sum = one;
+    let mut term = one;
+    let mut n = 1i128;
+    while term != 0 {
+        term = -term * r / (n * one);
+        sum += term;
+        n += 1;
+    }
+
+    ((sum as u128) >> k) / GUARD
+}
+
+/// ln(y) for a fixed-point `y >= 1`, returned at `SCALE` precision.
+///
+/// Normalises y = 2^k * m with m in [1, 2) and evaluates ln(m) through the
+/// atanh series 2 * (z + z^3/3 + z^5/5 + ...) with z = (m - 1) / (m + 1),
+/// with `GUARD` extra digits and rounded down; absolute error is below 1 unit
+/// of `SCALE`.
+pub fn ln(y: u128) -> Result<u128> {
+    require!(y >= SCALE, PredictionMarketError::ArithmeticUnderflow);
+
+    let k = (y / SCALE).ilog2();
+    let one = SCALE * GUARD;
+    let m = mul_div(y, GUARD, 1 << k, Rounding::Down)?;
+    let z = (m - one) * one / (m + one);
+    let z2 = z * z / one;
+    let mut sum = z;
+    let mut term = z;
+    let mut i = 3u128;
+    loop {
+        term = term * z2 / one;
+        if term == 0 {
+            break;
+        }
+        sum += term / i;
+        i += 2;
+    }
+
+    let guarded = (k as u128)
+        .checked_mul(LN_2_GUARDED)
+        .and_then(|v| v.checked_add(2 * sum))
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    Ok(guarded / GUARD)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LN_2: u128 = LN_2_GUARDED / GUARD;
+
+    fn scaled(value: f64) -> f64 {
+        value * SCALE as f64
+    }
+
+    #[test]
+    fn isqrt_is_exact_floor() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(1), 1);
+        assert_eq!(isqrt(2), 1);
+        assert_eq!(isqrt(u64::MAX as u128), u32::MAX as u128);
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+
+        for root in [2u128, 3, 1_000, 1 << 31, u32::MAX as u128, 1 << 63, u64::MAX as u128] {
+            let square = root * root;
+            assert_eq!(isqrt(square), root);
+            assert_eq!(isqrt(square - 1), root - 1);
+            assert_eq!(isqrt(square + 1), root);
+        }
+
+        for n in [5u128, 99, 12_345_678_901, u64::MAX as u128 * 3, u128::MAX / 7] {
+            let root = isqrt(n);
+            assert!(root * root <= n);
+            assert!((root + 1).checked_mul(root + 1).is_none_or(|next| next > n));
+        }
+    }
+
+    #[test]
+    fn u192_carries_full_products() {
+        assert_eq!(U192::from_u128(u128::MAX).to_u128(), Some(u128::MAX));
+        assert_eq!(U192::checked_mul(0, u128::MAX), Some(U192::default()));
+        assert_eq!(U192::checked_mul(1, u128::MAX), Some(U192::from_u128(u128::MAX)));
+
+        // (2^128 - 1) * (2^64 - 1) needs all 192 bits and divides back exactly
+        let product = U192::checked_mul(u128::MAX, u64::MAX as u128).unwrap();
+        assert_eq!(product.to_u128(), None);
+        assert_eq!(product.div_rem(u64::MAX as u128), (U192::from_u128(u128::MAX), 0));
+        assert_eq!(product.div_rem(u128::MAX), (U192::from_u128(u64::MAX as u128), 0));
+
+        let (quotient, remainder) = U192::checked_mul(u128::MAX, 10).unwrap().div_rem(7);
+        assert_eq!(remainder, (u128::MAX % 7 * 10) % 7);
+        assert_eq!(quotient.to_u128(), None);
+
+        assert_eq!(U192::checked_mul(u128::MAX, u128::MAX), None);
+    }
+
+    #[test]
+    fn mul_div_rounds_in_the_requested_direction() {
+        assert_eq!(mul_div(7, 3, 2, Rounding::Down).unwrap(), 10);
+        assert_eq!(mul_div(7, 3, 2, Rounding::Up).unwrap(), 11);
+        assert_eq!(mul_div(8, 3, 2, Rounding::Up).unwrap(), 12);
+        assert_eq!(mul_div(0, u128::MAX, 1, Rounding::Up).unwrap(), 0);
+
+        // Intermediate products beyond u128
+        assert_eq!(mul_div(u128::MAX, u64::MAX as u128, u64::MAX as u128, Rounding::Down).unwrap(), u128::MAX);
+        assert_eq!(mul_div(u128::MAX, 3, 3, Rounding::Up).unwrap(), u128::MAX);
+        let floor = u128::MAX / 7 * 2;
+        assert_eq!(mul_div(u128::MAX, 2, 7, Rounding::Down).unwrap(), floor);
+        assert_eq!(mul_div(u128::MAX, 2, 7, Rounding::Up).unwrap(), floor + 1);
+
+        assert!(mul_div(1, 1, 0, Rounding::Down).is_err());
+        assert!(mul_div(u128::MAX, 2, 1, Rounding::Down).is_err());
+        assert!(mul_div(u128::MAX, u64::MAX as u128, u64::MAX as u128 - 1, Rounding::Down).is_err());
+        // Products beyond 192 bits are rejected rather than truncated
+        assert!(mul_div(u128::MAX, u128::MAX, u128::MAX, Rounding::Down).is_err());
+    }
+
+    #[test]
+    fn token_helpers_stay_within_u64() {
+        assert_eq!(mul_div_u64(u64::MAX, u64::MAX, u64::MAX, Rounding::Down).unwrap(), u64::MAX);
+        assert_eq!(mul_div_u64(u64::MAX, 1, 2, Rounding::Up).unwrap(), u64::MAX / 2 + 1);
+        assert!(mul_div_u64(u64::MAX, 2, 1, Rounding::Down).is_err());
+
+        assert_eq!(apply_bps(1, 1, Rounding::Down).unwrap(), 0);
+        assert_eq!(apply_bps(1, 1, Rounding::Up).unwrap(), 1);
+        assert_eq!(apply_bps(u64::MAX, 10000, Rounding::Up).unwrap(), u64::MAX);
+        assert_eq!(apply_bps(u64::MAX, 10000, Rounding::Down).unwrap(), u64::MAX);
+
+        assert_eq!(to_units(SCALE + 1, Rounding::Down).unwrap(), 1);
+        assert_eq!(to_units(SCALE + 1, Rounding::Up).unwrap(), 2);
+        assert_eq!(fixed_mul(SCALE, SCALE, Rounding::Down).unwrap(), SCALE);
+        assert_eq!(fixed_div(SCALE, 3 * SCALE, Rounding::Up).unwrap(), SCALE / 3 + 1);
+    }
+
+    #[test]
+    fn exp_neg_is_within_one_unit() {
+        assert_eq!(exp_neg(0), SCALE);
+        assert!(exp_neg(1) < SCALE);
+        assert_eq!(exp_neg(MAX_EXP_INPUT + 1), 0);
+        assert_eq!(exp_neg(u128::MAX), 0);
+
+        for x in [1u128, 2, SCALE / 1000, LN_2 - 1, LN_2, LN_2 + 1, SCALE - 1, SCALE, SCALE + 1, 5 * SCALE, 17 * SCALE + 3, MAX_EXP_INPUT] {
+            let expected = scaled((-(x as f64) / SCALE as f64).exp());
+            let actual = exp_neg(x) as f64;
+            assert!((actual - expected).abs() <= 1.0, "exp(-{}): {} vs {}", x, actual, expected);
+        }
+
+        let mut previous = SCALE;
+        for x in (0..=10 * SCALE).step_by((SCALE / 7) as usize) {
+            let value = exp_neg(x);
+            assert!(value <= previous);
+            previous = value;
+        }
+    }
+
+    #[test]
+    fn ln_is_within_one_unit() {
+        assert_eq!(ln(SCALE).unwrap(), 0);
+        assert!(ln(SCALE - 1).is_err());
+        assert!(ln(0).is_err());
+        assert_eq!(ln(2 * SCALE).unwrap(), LN_2);
+
+        for y in [SCALE + 1, SCALE + 1_000, 2 * SCALE - 1, 2 * SCALE, 3 * SCALE, 10 * SCALE, 1_000_000 * SCALE, u64::MAX as u128 * SCALE] {
+            let expected = scaled(((y - SCALE) as f64 / SCALE as f64).ln_1p());
+            let actual = ln(y).unwrap() as f64;
+            assert!((actual - expected).abs() <= 1.0, "ln({}): {} vs {}", y, actual, expected);
+        }
+
+        // ln undoes exp_neg up to the combined error
+        for x in [SCALE / 3, SCALE, 4 * SCALE] {
+            let y = fixed_div(SCALE, exp_neg(x), Rounding::Down).unwrap();
+            assert!(ln(y).unwrap().abs_diff(x) < SCALE / 1_000_000, "ln(exp({}))", x);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::pubkey;
+
+use crate::errors::PredictionMarketError;
+
+/// Pyth Solana receiver program, owner of every `PriceUpdateV2` account
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+pub type FeedId = [u8; 32];
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum VerificationLevel {
+    Partial { num_signatures: u8 },
+    Full,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Debug)]
+pub struct PriceFeedMessage {
+    pub feed_id: FeedId,
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub prev_publish_time: i64,
+    pub ema_price: i64,
+    pub ema_conf: u64,
+}
+
+/// Price update posted by the Pyth receiver program. Only the account layout
+/// is needed to settle markets, so it is decoded here rather than through the
+/// receiver SDK and its dependency tree.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PriceUpdateV2 {
+    pub write_authority: Pubkey,
+    pub verification_level: VerificationLevel,
+    pub price_message: PriceFeedMessage,
+    pub posted_slot: u64,
+}
+
+impl Discriminator for PriceUpdateV2 {
+    const DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+}
+
+impl Owner for PriceUpdateV2 {
+    fn owner() -> Pubkey {
+        PYTH_RECEIVER_PROGRAM_ID
+    }
+}
+
+impl AccountSerialize for PriceUpdateV2 {}
+
+impl AccountDeserialize for PriceUpdateV2 {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        require!(
+            buf.len() >= 8 && buf[..8] == Self::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Self::try_deserialize_unchecked(buf)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        let mut data = buf.get(8..).ok_or(ErrorCode::AccountDidNotDeserialize)?;
+        AnchorDeserialize::deserialize(&mut data)
+            .map_err(|_| PredictionMarketError::InvalidPriceFeed.into())
+    }
+}
+
+impl PriceUpdateV2 {
+    /// The posted price for `feed_id`, provided it was published no earlier
+    /// than `start` and at most `maximum_age` seconds after it
+    pub fn get_price_published_after(
+        &self,
+        start: i64,
+        maximum_age: u64,
+        feed_id: &FeedId,
+    ) -> Result<PriceFeedMessage> {
+        require!(
+            self.price_message.feed_id == *feed_id,
+            PredictionMarketError::InvalidPriceFeed
+        );
+        let publish_time = self.price_message.publish_time;
+        require!(
+            publish_time >= start && publish_time <= start.saturating_add(maximum_age as i64),
+            PredictionMarketError::StaleOracleData
+        );
+
+        Ok(self.price_message)
+    }
+}
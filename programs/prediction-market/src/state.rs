@@ -1,64 +1,250 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::PredictionMarketError;
+use crate::math::{apply_bps, mul_div_u64, Rounding};
+
 #[account]
 pub struct Market {
-    pub authority: Pubkey,
+    pub market_id: String,
+    pub creator: Pubkey,
     pub title: String,
     pub description: String,
     pub category: String,
+    pub resolution_source: String,
     pub oracle: Pubkey,
-    pub resolution_time: i64,
+    pub token_mint: Pubkey,
+    pub market_vault: Pubkey,
+    pub outcome_a_mint: Pubkey,
+    pub outcome_b_mint: Pubkey,
     pub created_at: i64,
+    pub end_time: i64,
+    pub resolution_time: i64,
+    pub claim_deadline: i64, // set when the market settles; after this, unclaimed funds can be swept
+    pub sweep_destination: Option<Pubkey>, // owner of the sweep token account, platform authority if None
     pub resolved_at: Option<i64>,
-    pub outcome: Option<u8>,
+    pub winning_outcome: Option<u8>,
+    pub min_bet_amount: u64,
+    pub max_bet_amount: u64,
     pub total_volume: u64,
     pub total_liquidity: u64,
-    pub fee_rate: u16, // basis points (e.g., 250 = 2.5%)
+    pub total_bets: u64,
+    pub total_claimed: u64,
+    pub bets_paid: u64, // parimutuel winnings and refunds paid from the vault
+    pub positions_paid: u64, // LMSR position payouts paid from the vault
+    pub last_bet_time: i64,
+    pub creator_fee_bps: u16,
+    pub platform_fee_bps: u16,
+    pub oracle_fee_bps: u16,
+    pub creator_fees_accrued: u64, // unwithdrawn balances held in the protocol fee account
+    pub platform_fees_accrued: u64,
+    pub oracle_fees_accrued: u64,
+    pub cancellation_window: i64, // seconds after a bet during which it can be cancelled
+    pub cancellation_fee_bps: u16,
+    pub cancellation_lock_time: i64, // 0 when only the per-bet window applies
+    pub payout_ratio: u64, // basis points of net pool paid per unit of winning stake
+    pub lmsr_b: u64, // LMSR liquidity parameter, 0 for pure parimutuel markets
+    pub lmsr_funding: u64, // creator subsidy covering the b * ln(n) worst-case loss
+    pub market_type: MarketType,
+    pub lower_bound: i64, // scalar markets only
+    pub upper_bound: i64, // scalar markets only
+    pub resolved_value: Option<i64>, // reported value clamped to bounds, scalar markets only
+    pub resolved_invalid: bool, // resolved Invalid: stakes refunded instead of paying a winner
+    pub price_feed: Option<PriceFeed>, // set for markets settled from a price oracle
+    pub oracle_committee: Option<Pubkey>, // set once an M-of-N committee replaces the single oracle
+    pub oracle_data: Vec<u8>,
     pub status: MarketStatus,
     pub outcomes: Vec<Outcome>,
     pub bump: u8,
+    pub vault_bump: u8,
 }
 
 impl Market {
-    pub const MAX_TITLE_LENGTH: usize = 100;
-    pub const MAX_DESCRIPTION_LENGTH: usize = 500;
-    pub const MAX_CATEGORY_LENGTH: usize = 50;
+    pub const MAX_MARKET_ID_LENGTH: usize = 32;
+    pub const MAX_TITLE_LENGTH: usize = 128;
+    pub const MAX_DESCRIPTION_LENGTH: usize = 512;
+    pub const MAX_CATEGORY_LENGTH: usize = 32;
+    pub const MAX_RESOLUTION_SOURCE_LENGTH: usize = 128;
+    pub const MAX_ORACLE_DATA_LENGTH: usize = 256;
     pub const MAX_OUTCOMES: usize = 10;
+    pub const MAX_CANCELLATION_FEE_BPS: u16 = 1000;
+    /// Time after `resolution_time` an unresolved market may be expired
+    pub const RESOLUTION_WINDOW: i64 = 7 * 24 * 60 * 60;
+    /// Outcome index an oracle reports to resolve the market Invalid
+    pub const INVALID_OUTCOME: u8 = u8::MAX;
+    /// Time claimants have after the market resolves or is cancelled before
+    /// funds can be swept
+    pub const CLAIM_PERIOD: i64 = 90 * 24 * 60 * 60;
+
+    /// Start the claim period once the market resolves or is cancelled
+    pub fn open_claims(&mut self, settled_at: i64) {
+        self.claim_deadline = settled_at.saturating_add(Self::CLAIM_PERIOD);
+    }
     
     pub fn space() -> usize {
         8 + // discriminator
-        32 + // authority
+        4 + Self::MAX_MARKET_ID_LENGTH + // market_id
+        32 + // creator
         4 + Self::MAX_TITLE_LENGTH + // title
         4 + Self::MAX_DESCRIPTION_LENGTH + // description
         4 + Self::MAX_CATEGORY_LENGTH + // category
+        4 + Self::MAX_RESOLUTION_SOURCE_LENGTH + // resolution_source
         32 + // oracle
-        8 + // resolution_time
+        32 + // token_mint
+        32 + // market_vault
+        32 + // outcome_a_mint
+        32 + // outcome_b_mint
         8 + // created_at
+        8 + // end_time
+        8 + // resolution_time
+        8 + // claim_deadline
+        1 + 32 + // sweep_destination (Option<Pubkey>)
         1 + 8 + // resolved_at (Option<i64>)
-        1 + 1 + // outcome (Option<u8>)
+        1 + 1 + // winning_outcome (Option<u8>)
+        8 + // min_bet_amount
+        8 + // max_bet_amount
         8 + // total_volume
         8 + // total_liquidity
-        2 + // fee_rate
+        8 + // total_bets
+        8 + // total_claimed
+        8 + // bets_paid
+        8 + // positions_paid
+        8 + // last_bet_time
+        2 + // creator_fee_bps
+        2 + // platform_fee_bps
+        2 + // oracle_fee_bps
+        8 + // creator_fees_accrued
+        8 + // platform_fees_accrued
+        8 + // oracle_fees_accrued
+        8 + // cancellation_window
+        2 + // cancellation_fee_bps
+        8 + // cancellation_lock_time
+        8 + // payout_ratio
+        8 + // lmsr_b
+        8 + // lmsr_funding
+        1 + // market_type
+        8 + // lower_bound
+        8 + // upper_bound
+        1 + 8 + // resolved_value (Option<i64>)
+        1 + // resolved_invalid
+        1 + PriceFeed::space() + // price_feed (Option<PriceFeed>)
+        1 + 32 + // oracle_committee (Option<Pubkey>)
+        4 + Self::MAX_ORACLE_DATA_LENGTH + // oracle_data
         1 + // status
         4 + (Self::MAX_OUTCOMES * Outcome::space()) + // outcomes
-        1 // bump
+        1 + // bump
+        1 // vault_bump
+    }
+
+    /// Whether claims are open: the market resolved, or was cancelled and
+    /// refunds every product the way an Invalid resolution does
+    pub fn is_settled(&self) -> bool {
+        matches!(self.status, MarketStatus::Resolved | MarketStatus::Cancelled)
+    }
+
+    /// Whether the market refunds its products instead of paying a winner
+    pub fn is_refunding(&self) -> bool {
+        self.resolved_invalid || self.status == MarketStatus::Cancelled
+    }
+
+    /// Collateral paid per resolved share of `outcome`, in basis points.
+    ///
+    /// Categorical markets pay 10000 to the winning outcome; scalar markets
+    /// pay LONG (outcome 0) linearly across the bounds and SHORT the rest.
+    /// An Invalid or cancelled market splits the unit of collateral equally
+    /// across outcomes.
+    pub fn payout_bps(&self, outcome: u8) -> u64 {
+        if self.is_refunding() {
+            return if (outcome as usize) < self.outcomes.len() {
+                10000 / self.outcomes.len() as u64
+            } else {
+                0
+            };
+        }
+
+        match self.market_type {
+            MarketType::Categorical => {
+                if self.winning_outcome == Some(outcome) { 10000 } else { 0 }
+            }
+            MarketType::Scalar => {
+                let Some(value) = self.resolved_value else { return 0 };
+                let range = (self.upper_bound as i128 - self.lower_bound as i128) as u128;
+                let offset = (value as i128 - self.lower_bound as i128) as u128;
+                let long_bps = (offset * 10000 / range) as u64;
+                match outcome {
+                    0 => long_bps,
+                    1 => 10000 - long_bps,
+                    _ => 0,
+                }
+            }
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum MarketType {
+    Categorical,
+    Scalar, // LONG/SHORT pair settled against a reported value
+}
+
+/// Price oracle a market settles against. Binary markets resolve YES when the
+/// comparison holds; scalar markets settle at the observed price. Thresholds
+/// and scalar bounds are fixed-point at `exponent`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct PriceFeed {
+    pub source: PriceFeedSource,
+    pub feed_id: [u8; 32], // Pyth price feed id or Switchboard aggregator address
+    pub comparison: PriceComparison,
+    pub threshold: i64, // lower threshold for Between
+    pub upper_threshold: i64, // Between only
+    pub exponent: i32,
+    pub max_staleness: i64, // seconds, 0 for the source default
+    pub min_oracle_responses: u32, // Switchboard only
+}
+
+impl PriceFeed {
+    pub fn space() -> usize {
+        1 + // source
+        32 + // feed_id
+        1 + // comparison
+        8 + // threshold
+        8 + // upper_threshold
+        4 + // exponent
+        8 + // max_staleness
+        4 // min_oracle_responses
     }
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum PriceFeedSource {
+    Pyth,
+    Switchboard,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum PriceComparison {
+    Above,
+    Below,
+    Between,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 pub enum MarketStatus {
     Active,
     Paused,
     Resolved,
     Cancelled,
+    Swept, // unclaimed funds swept; the account stays as a tombstone for its market_id and leftover accounts
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct Outcome {
     pub id: u8,
     pub title: String,
+    pub total_amount: u64,
+    pub bet_count: u64,
+    pub current_odds: u64, // basis points (e.g., 20000 = 2.0x)
     pub total_shares: u64,
-    pub price: u64, // in lamports per share
+    pub price: u64, // basis points of collateral per share
     pub last_price: u64,
     pub volume_24h: u64,
 }
@@ -69,6 +255,9 @@ impl Outcome {
     pub fn space() -> usize {
         1 + // id
         4 + Self::MAX_TITLE_LENGTH + // title
+        8 + // total_amount
+        8 + // bet_count
+        8 + // current_odds
         8 + // total_shares
         8 + // price
         8 + // last_price
@@ -76,6 +265,211 @@ impl Outcome {
     }
 }
 
+#[account]
+pub struct Bet {
+    pub bettor: Pubkey,
+    pub market: Pubkey,
+    pub outcomes: Vec<BetOutcome>,
+    pub total_amount: u64,
+    pub created_at: i64,
+    pub last_bet_at: i64,
+    pub claimed: bool,
+    pub bump: u8,
+}
+
+impl Bet {
+    pub fn space() -> usize {
+        8 + // discriminator
+        32 + // bettor
+        32 + // market
+        4 + (Market::MAX_OUTCOMES * BetOutcome::space()) + // outcomes
+        8 + // total_amount
+        8 + // created_at
+        8 + // last_bet_at
+        1 + // claimed
+        1 // bump
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct BetOutcome {
+    pub amount: u64,
+    pub odds_at_bet: u64, // basis points
+    pub bet_count: u64,
+    pub cancellable_since: i64, // start of the current cancellation window
+    pub cancellable_amount: u64, // stake placed since `cancellable_since`
+}
+
+impl BetOutcome {
+    pub fn space() -> usize {
+        8 + // amount
+        8 + // odds_at_bet
+        8 + // bet_count
+        8 + // cancellable_since
+        8 // cancellable_amount
+    }
+}
+
+#[account]
+pub struct MarketStats {
+    pub market: Pubkey,
+    pub total_volume: u64,
+    pub total_liquidity: u64,
+    pub yes_volume: u64,
+    pub no_volume: u64,
+    pub unique_traders: u32,
+    pub total_trades: u64,
+    pub last_trade_price: u64, // basis points
+    pub yes_price: u64, // basis points
+    pub no_price: u64, // basis points
+    pub bump: u8,
+}
+
+impl MarketStats {
+    pub fn space() -> usize {
+        8 + // discriminator
+        32 + // market
+        8 + // total_volume
+        8 + // total_liquidity
+        8 + // yes_volume
+        8 + // no_volume
+        4 + // unique_traders
+        8 + // total_trades
+        8 + // last_trade_price
+        8 + // yes_price
+        8 + // no_price
+        1 // bump
+    }
+}
+
+#[account]
+pub struct OutcomeTokens {
+    pub market: Pubkey,
+    pub yes_token_supply: u64,
+    pub no_token_supply: u64,
+    pub yes_token_vault: Pubkey,
+    pub no_token_vault: Pubkey,
+    pub bump: u8,
+}
+
+impl OutcomeTokens {
+    pub fn space() -> usize {
+        8 + // discriminator
+        32 + // market
+        8 + // yes_token_supply
+        8 + // no_token_supply
+        32 + // yes_token_vault
+        32 + // no_token_vault
+        1 // bump
+    }
+}
+
+#[account]
+pub struct MarketResolution {
+    pub market: Pubkey,
+    pub resolved: bool,
+    pub resolution_time: i64,
+    pub winning_outcome: Option<u8>,
+    pub resolver: Option<Pubkey>,
+    pub resolution_data: String,
+    pub dispute_period_end: i64,
+    pub attestation_nonce: u64, // highest oracle attestation nonce accepted
+    pub bond_amount: u64, // optimistic proposal bond, 0 disables proposals
+    pub dispute_window: i64, // seconds a proposal stays open to dispute
+    pub proposer: Option<Pubkey>,
+    pub proposed_outcome: Option<u8>,
+    pub proposed_at: i64,
+    pub disputer: Option<Pubkey>,
+    pub disputed_outcome: Option<u8>,
+    pub bump: u8,
+}
+
+impl MarketResolution {
+    pub const MAX_RESOLUTION_DATA_LENGTH: usize = 256;
+    
+    pub fn space() -> usize {
+        8 + // discriminator
+        32 + // market
+        1 + // resolved
+        8 + // resolution_time
+        1 + 1 + // winning_outcome (Option<u8>)
+        1 + 32 + // resolver (Option<Pubkey>)
+        4 + Self::MAX_RESOLUTION_DATA_LENGTH + // resolution_data
+        8 + // dispute_period_end
+        8 + // attestation_nonce
+        8 + // bond_amount
+        8 + // dispute_window
+        1 + 32 + // proposer (Option<Pubkey>)
+        1 + 1 + // proposed_outcome (Option<u8>)
+        8 + // proposed_at
+        1 + 32 + // disputer (Option<Pubkey>)
+        1 + 1 + // disputed_outcome (Option<u8>)
+        1 // bump
+    }
+}
+
+/// Off-chain oracle attestation carried in `oracle_data`; the signature is
+/// supplied through a preceding Ed25519 program instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OracleAttestation {
+    pub timestamp: i64,
+    pub nonce: u64,
+}
+
+#[account]
+pub struct OracleCommittee {
+    pub market: Pubkey,
+    pub members: Vec<Pubkey>, // registered Oracle accounts allowed to vote
+    pub threshold: u8, // votes an outcome needs to resolve the market
+    pub vote_deadline: i64, // conflicting votes past this fail with ConsensusNotReached
+    pub tallies: Vec<u8>, // votes received per outcome
+    pub total_votes: u8,
+    pub bump: u8,
+}
+
+impl OracleCommittee {
+    pub const MAX_MEMBERS: usize = 10;
+
+    pub fn space() -> usize {
+        8 + // discriminator
+        32 + // market
+        4 + (Self::MAX_MEMBERS * 32) + // members
+        1 + // threshold
+        8 + // vote_deadline
+        4 + Market::MAX_OUTCOMES + // tallies
+        1 + // total_votes
+        1 // bump
+    }
+
+    /// Outcome that has reached the threshold, if any
+    pub fn quorum_outcome(&self) -> Option<u8> {
+        self.tallies
+            .iter()
+            .position(|votes| *votes >= self.threshold)
+            .map(|outcome| outcome as u8)
+    }
+}
+
+#[account]
+pub struct OracleVote {
+    pub market: Pubkey,
+    pub oracle: Pubkey, // Oracle account that cast the vote
+    pub outcome: u8,
+    pub voted_at: i64,
+    pub bump: u8,
+}
+
+impl OracleVote {
+    pub fn space() -> usize {
+        8 + // discriminator
+        32 + // market
+        32 + // oracle
+        1 + // outcome
+        8 + // voted_at
+        1 // bump
+    }
+}
+
 #[account]
 pub struct Position {
     pub owner: Pubkey,
@@ -104,6 +498,27 @@ impl Position {
         8 + // unrealized_pnl
         1 // bump
     }
+
+    /// Collateral paid for `shares` at the average entry price, rounded up
+    /// so realised PnL is never overstated
+    pub fn cost_basis(&self, shares: u64) -> Result<u64> {
+        apply_bps(shares, self.average_price, Rounding::Up)
+    }
+
+    /// Add `shares` bought for `cost`, re-weighting the average entry price
+    pub fn add_shares(&mut self, shares: u64, cost: u64) -> Result<()> {
+        let new_shares = self.shares
+            .checked_add(shares)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        let cost_basis = self.cost_basis(self.shares)?
+            .checked_add(cost)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+        self.average_price = mul_div_u64(cost_basis, 10000, new_shares, Rounding::Up)?;
+        self.shares = new_shares;
+
+        Ok(())
+    }
 }
 
 #[account]
@@ -137,9 +552,26 @@ impl UserProfile {
 #[account]
 pub struct LiquidityPool {
     pub market: Pubkey,
-    pub outcome_id: u8,
+    pub liquidity_vault: Pubkey,
+    pub lp_token_mint: Pubkey,
+    pub outcome_a_vault: Pubkey,
+    pub outcome_b_vault: Pubkey,
     pub total_liquidity: u64,
     pub available_liquidity: u64,
+    pub yes_reserves: u64,
+    pub no_reserves: u64,
+    pub k_constant: u128,
+    pub total_lp_tokens: u64,
+    pub fee_rate_bps: u16,
+    pub withdrawal_fee_bps: u16,
+    pub total_fees_collected: u64,
+    pub fee_per_share: u128, // cumulative LP fees per LP token, scaled by FEE_PER_SHARE_SCALE
+    pub unclaimed_fees: u64, // accrued LP fees still held in the liquidity vault
+    pub settled: bool, // reserves converted to collateral after resolution
+    pub settlement_value: u64, // collateral still owed to LPs once settled
+    pub yes_shares_outstanding: u64, // AMM shares held by traders, claimable from the liquidity vault
+    pub no_shares_outstanding: u64,
+    pub active_providers: u32,
     pub utilization_rate: u16, // basis points
     pub apr: u16, // basis points
     pub created_at: i64,
@@ -148,27 +580,110 @@ pub struct LiquidityPool {
 }
 
 impl LiquidityPool {
+    pub const FEE_PER_SHARE_SCALE: u128 = 1_000_000_000_000;
+    pub const SECONDS_PER_YEAR: u128 = 365 * 24 * 60 * 60;
+    /// LP tokens locked in the pool for good when it is seeded, so the LP
+    /// supply can never be drained back to zero and its price reset
+    pub const MINIMUM_LIQUIDITY: u64 = 1000;
+
     pub fn space() -> usize {
         8 + // discriminator
         32 + // market
-        1 + // outcome_id
+        32 + // liquidity_vault
+        32 + // lp_token_mint
+        32 + // outcome_a_vault
+        32 + // outcome_b_vault
         8 + // total_liquidity
         8 + // available_liquidity
+        8 + // yes_reserves
+        8 + // no_reserves
+        16 + // k_constant
+        8 + // total_lp_tokens
+        2 + // fee_rate_bps
+        2 + // withdrawal_fee_bps
+        8 + // total_fees_collected
+        16 + // fee_per_share
+        8 + // unclaimed_fees
+        1 + // settled
+        8 + // settlement_value
+        8 + // yes_shares_outstanding
+        8 + // no_shares_outstanding
+        4 + // active_providers
         2 + // utilization_rate
         2 + // apr
         8 + // created_at
         8 + // last_updated
         1 // bump
     }
+
+    /// AMM shares of `outcome` held by traders
+    pub fn shares_outstanding_mut(&mut self, outcome: u8) -> &mut u64 {
+        if outcome == 0 {
+            &mut self.yes_shares_outstanding
+        } else {
+            &mut self.no_shares_outstanding
+        }
+    }
+
+    /// Credit a trading fee retained in the liquidity vault to current LPs.
+    /// With no LP tokens outstanding the fee simply stays in the pool.
+    pub fn accrue_lp_fees(&mut self, fee: u64) -> Result<()> {
+        self.total_fees_collected = self.total_fees_collected
+            .checked_add(fee)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+        if self.total_lp_tokens == 0 || fee == 0 {
+            return Ok(());
+        }
+
+        let increment = (fee as u128)
+            .checked_mul(Self::FEE_PER_SHARE_SCALE)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?
+            / self.total_lp_tokens as u128;
+        self.fee_per_share = self.fee_per_share
+            .checked_add(increment)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        self.unclaimed_fees = self.unclaimed_fees
+            .checked_add(fee)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    /// Recompute `utilization_rate` and `apr` after a trade.
+    ///
+    /// Utilization is the share of the pool's outcome inventory carried as net
+    /// trader exposure, |yes - no| / (yes + no). APR annualizes the fees
+    /// collected since the pool opened against its current liquidity.
+    pub fn update_yield_metrics(&mut self, now: i64) {
+        let total_reserves = self.yes_reserves as u128 + self.no_reserves as u128;
+        self.utilization_rate = (self.yes_reserves.abs_diff(self.no_reserves) as u128 * 10000)
+            .checked_div(total_reserves)
+            .unwrap_or(0) as u16;
+
+        let elapsed = now.saturating_sub(self.created_at).max(1) as u128;
+        self.apr = if self.total_liquidity > 0 {
+            (self.total_fees_collected as u128 * 10000 * Self::SECONDS_PER_YEAR
+                / (self.total_liquidity as u128 * elapsed))
+                .min(u16::MAX as u128) as u16
+        } else {
+            0
+        };
+    }
 }
 
 #[account]
 pub struct LiquidityPosition {
     pub owner: Pubkey,
+    pub market: Pubkey,
     pub pool: Pubkey,
-    pub shares: u64,
+    pub lp_tokens: u64,
     pub deposited_amount: u64,
-    pub earned_fees: u64,
+    pub outcome_a_contributed: u64,
+    pub outcome_b_contributed: u64,
+    pub earned_fees: u64, // settled but unclaimed LP fees
+    pub fee_debt: u128, // lp_tokens * fee_per_share at the last settlement
+    pub is_active: bool,
     pub created_at: i64,
     pub last_updated: i64,
     pub bump: u8,
@@ -178,14 +693,45 @@ impl LiquidityPosition {
     pub fn space() -> usize {
         8 + // discriminator
         32 + // owner
+        32 + // market
         32 + // pool
-        8 + // shares
+        8 + // lp_tokens
         8 + // deposited_amount
+        8 + // outcome_a_contributed
+        8 + // outcome_b_contributed
         8 + // earned_fees
+        16 + // fee_debt
+        1 + // is_active
         8 + // created_at
         8 + // last_updated
         1 // bump
     }
+    /// Move fees accrued since the last settlement into `earned_fees`; call
+    /// before `lp_tokens` changes and follow with `sync_fee_debt`
+    pub fn settle_fees(&mut self, pool: &LiquidityPool) -> Result<()> {
+        let accrued = (self.lp_tokens as u128)
+            .checked_mul(pool.fee_per_share)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?
+            / LiquidityPool::FEE_PER_SHARE_SCALE;
+        let pending = accrued.saturating_sub(self.fee_debt);
+
+        self.earned_fees = self.earned_fees
+            .checked_add(u64::try_from(pending).map_err(|_| PredictionMarketError::ArithmeticOverflow)?)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        self.fee_debt = accrued;
+
+        Ok(())
+    }
+
+    /// Reset the fee checkpoint after `lp_tokens` changed
+    pub fn sync_fee_debt(&mut self, pool: &LiquidityPool) -> Result<()> {
+        self.fee_debt = (self.lp_tokens as u128)
+            .checked_mul(pool.fee_per_share)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?
+            / LiquidityPool::FEE_PER_SHARE_SCALE;
+
+        Ok(())
+    }
 }
 
 #[account]
@@ -224,6 +770,62 @@ pub enum TradeType {
     Sell,
 }
 
+#[account]
+pub struct OrderBook {
+    pub market: Pubkey,
+    pub outcome_id: u8,
+    pub book_vault: Pubkey,
+    pub bids: Vec<Order>, // highest price first, FIFO within a price
+    pub asks: Vec<Order>, // lowest price first, FIFO within a price
+    pub fills: Vec<Trade>, // most recent fills, oldest first
+    pub next_order_id: u64,
+    pub total_fills: u64,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl OrderBook {
+    pub const MAX_ORDERS_PER_SIDE: usize = 32;
+    pub const MAX_RECENT_FILLS: usize = 16;
+    pub const MIN_PRICE: u64 = 1; // basis points
+    pub const MAX_PRICE: u64 = 9999; // basis points
+
+    pub fn space() -> usize {
+        8 + // discriminator
+        32 + // market
+        1 + // outcome_id
+        32 + // book_vault
+        4 + (Self::MAX_ORDERS_PER_SIDE * Order::space()) + // bids
+        4 + (Self::MAX_ORDERS_PER_SIDE * Order::space()) + // asks
+        4 + (Self::MAX_RECENT_FILLS * (Trade::space() - 8)) + // fills
+        8 + // next_order_id
+        8 + // total_fills
+        1 + // bump
+        1 // vault_bump
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct Order {
+    pub order_id: u64,
+    pub owner: Pubkey,
+    pub price: u64, // basis points of collateral per share
+    pub quantity: u64,
+    pub collateral_locked: u64, // escrowed collateral for bids, zero for asks
+    pub created_at: i64,
+}
+
+impl Order {
+    pub fn space() -> usize {
+        8 + // order_id
+        32 + // owner
+        8 + // price
+        8 + // quantity
+        8 + // collateral_locked
+        8 // created_at
+    }
+}
+
 #[account]
 pub struct Oracle {
     pub authority: Pubkey,
@@ -240,6 +842,7 @@ pub struct Oracle {
 impl Oracle {
     pub const MAX_NAME_LENGTH: usize = 50;
     pub const MAX_DESCRIPTION_LENGTH: usize = 200;
+    pub const MAX_REPUTATION: u32 = 10000;
     
     pub fn space() -> usize {
         8 + // discriminator
@@ -253,6 +856,22 @@ impl Oracle {
         1 + // is_active
         1 // bump
     }
+
+    /// Accuracy in basis points, smoothed with one prior accurate and one
+    /// prior overturned resolution so a new oracle starts at 5000
+    pub fn reputation(accurate_resolutions: u32, total_resolutions: u32) -> u32 {
+        ((accurate_resolutions as u64 + 1) * Self::MAX_REPUTATION as u64
+            / (total_resolutions as u64 + 2)) as u32
+    }
+
+    /// Count a finished resolution against this oracle's track record
+    pub fn record_resolution(&mut self, accurate: bool) {
+        self.total_resolutions = self.total_resolutions.saturating_add(1);
+        if accurate {
+            self.accurate_resolutions = self.accurate_resolutions.saturating_add(1);
+        }
+        self.reputation_score = Self::reputation(self.accurate_resolutions, self.total_resolutions);
+    }
 }
 
 #[account]
@@ -266,10 +885,15 @@ pub struct GlobalState {
     pub min_market_duration: i64,
     pub max_market_duration: i64,
     pub paused: bool,
+    pub min_oracle_reputation: u32, // reputation floor for market oracles, 0 disables
     pub bump: u8,
 }
 
 impl GlobalState {
+    pub const MAX_PLATFORM_FEE_RATE: u16 = 500;
+    pub const MAX_ORACLE_FEE_RATE: u16 = 500;
+    pub const MAX_CREATOR_FEE_BPS: u16 = 1000;
+    
     pub fn space() -> usize {
         8 + // discriminator
         32 + // authority
@@ -281,6 +905,7 @@ impl GlobalState {
         8 + // min_market_duration
         8 + // max_market_duration
         1 + // paused
+        4 + // min_oracle_reputation
         1 // bump
     }
 }
@@ -298,4 +923,110 @@ impl PriceHistory {
         8 + // price
         8 // volume
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scalar_market(lower_bound: i64, upper_bound: i64, resolved_value: Option<i64>) -> Market {
+        Market {
+            market_id: "scalar".to_string(),
+            creator: Pubkey::default(),
+            title: String::new(),
+            description: String::new(),
+            category: String::new(),
+            resolution_source: String::new(),
+            oracle: Pubkey::default(),
+            token_mint: Pubkey::default(),
+            market_vault: Pubkey::default(),
+            outcome_a_mint: Pubkey::default(),
+            outcome_b_mint: Pubkey::default(),
+            created_at: 0,
+            end_time: 0,
+            resolution_time: 0,
+            claim_deadline: 0,
+            sweep_destination: None,
+            resolved_at: None,
+            winning_outcome: None,
+            min_bet_amount: 0,
+            max_bet_amount: 0,
+            total_volume: 0,
+            total_liquidity: 0,
+            total_bets: 0,
+            total_claimed: 0,
+            bets_paid: 0,
+            positions_paid: 0,
+            last_bet_time: 0,
+            creator_fee_bps: 0,
+            platform_fee_bps: 0,
+            oracle_fee_bps: 0,
+            creator_fees_accrued: 0,
+            platform_fees_accrued: 0,
+            oracle_fees_accrued: 0,
+            cancellation_window: 0,
+            cancellation_fee_bps: 0,
+            cancellation_lock_time: 0,
+            payout_ratio: 0,
+            lmsr_b: 0,
+            lmsr_funding: 0,
+            market_type: MarketType::Scalar,
+            lower_bound,
+            upper_bound,
+            resolved_value,
+            resolved_invalid: false,
+            price_feed: None,
+            oracle_committee: None,
+            oracle_data: Vec::new(),
+            status: MarketStatus::Resolved,
+            outcomes: Vec::new(),
+            bump: 0,
+            vault_bump: 0,
+        }
+    }
+
+    #[test]
+    fn scalar_payout_interpolates_between_bounds() {
+        let cases = [
+            (Some(100), 0),
+            (Some(200), 10000),
+            (Some(150), 5000),
+            (Some(125), 2500),
+            (Some(101), 100),
+        ];
+        for (value, long_bps) in cases {
+            let market = scalar_market(100, 200, value);
+            assert_eq!(market.payout_bps(0), long_bps);
+            assert_eq!(market.payout_bps(1), 10000 - long_bps);
+            assert_eq!(market.payout_bps(2), 0);
+        }
+    }
+
+    #[test]
+    fn scalar_payout_rounds_long_down() {
+        // 1/3 of the range: LONG floors to 3333 and SHORT takes the remainder
+        let market = scalar_market(0, 3, Some(1));
+        assert_eq!(market.payout_bps(0), 3333);
+        assert_eq!(market.payout_bps(1), 6667);
+    }
+
+    #[test]
+    fn scalar_payout_handles_extreme_bounds() {
+        // The full i64 range does not overflow the interpolation
+        let market = scalar_market(i64::MIN, i64::MAX, Some(0));
+        assert_eq!(market.payout_bps(0), 5000);
+        assert_eq!(market.payout_bps(1), 5000);
+        let market = scalar_market(i64::MIN, i64::MAX, Some(i64::MAX));
+        assert_eq!(market.payout_bps(0), 10000);
+
+        let market = scalar_market(-50, 50, Some(-25));
+        assert_eq!(market.payout_bps(0), 2500);
+    }
+
+    #[test]
+    fn unresolved_scalar_pays_nothing() {
+        let market = scalar_market(100, 200, None);
+        assert_eq!(market.payout_bps(0), 0);
+        assert_eq!(market.payout_bps(1), 0);
+    }
+}
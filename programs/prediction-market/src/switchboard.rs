@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_lang::__private::bytemuck::{Pod, Zeroable};
+use anchor_lang::{Discriminator, ZeroCopy};
+use anchor_lang::solana_program::pubkey;
+
+use crate::errors::PredictionMarketError;
+
+/// Switchboard V2 program, owner of every aggregator account
+pub const SWITCHBOARD_PROGRAM_ID: Pubkey = pubkey!("SW1TCH7qEPTdLsDHRgPuMQjbQxKdH2aBStViMFnt64f");
+
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default, Debug)]
+pub struct SwitchboardDecimal {
+    pub mantissa: i128,
+    pub scale: u32,
+}
+
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+pub struct AggregatorRound {
+    pub num_success: u32,
+    pub num_error: u32,
+    pub is_closed: u8,
+    pub round_open_slot: u64,
+    pub round_open_timestamp: i64,
+    pub result: SwitchboardDecimal,
+    pub std_deviation: SwitchboardDecimal,
+    pub min_response: SwitchboardDecimal,
+    pub max_response: SwitchboardDecimal,
+    pub oracle_pubkeys_data: [Pubkey; 16],
+    pub medians_data: [SwitchboardDecimal; 16],
+    pub current_payout: [i64; 16],
+    pub medians_fulfilled: [u8; 16],
+    pub errors_fulfilled: [u8; 16],
+}
+
+/// Leading fields of a Switchboard V2 aggregator, through its latest confirmed
+/// round. `AccountLoader` only maps `size_of::<Self>()` bytes, so the trailing
+/// job and history fields the program never reads are left out; only this
+/// layout is needed, not the Switchboard SDK and its dependency tree.
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+pub struct AggregatorAccountData {
+    pub name: [u8; 32],
+    pub metadata: [u8; 128],
+    pub _reserved1: [u8; 32],
+    pub queue_pubkey: Pubkey,
+    pub oracle_request_batch_size: u32,
+    pub min_oracle_results: u32,
+    pub min_job_results: u32,
+    pub min_update_delay_seconds: u32,
+    pub start_after: i64,
+    pub variance_threshold: SwitchboardDecimal,
+    pub force_report_period: i64,
+    pub expiration: i64,
+    pub consecutive_failure_count: u64,
+    pub next_allowed_update_time: i64,
+    pub is_locked: u8,
+    pub crank_pubkey: Pubkey,
+    pub latest_confirmed_round: AggregatorRound,
+}
+
+impl Discriminator for AggregatorAccountData {
+    const DISCRIMINATOR: [u8; 8] = [217, 230, 65, 101, 201, 162, 27, 125];
+}
+
+impl Owner for AggregatorAccountData {
+    fn owner() -> Pubkey {
+        SWITCHBOARD_PROGRAM_ID
+    }
+}
+
+// SAFETY: packed structs of integers, byte arrays and pubkeys, with booleans
+// read as `u8`, so there is no padding and every bit pattern is valid
+unsafe impl Pod for SwitchboardDecimal {}
+unsafe impl Zeroable for SwitchboardDecimal {}
+unsafe impl Pod for AggregatorRound {}
+unsafe impl Zeroable for AggregatorRound {}
+unsafe impl Pod for AggregatorAccountData {}
+unsafe impl Zeroable for AggregatorAccountData {}
+
+impl ZeroCopy for AggregatorAccountData {}
+
+impl AggregatorAccountData {
+    /// Result of the latest confirmed round, provided enough oracles responded
+    pub fn get_result(&self) -> Result<SwitchboardDecimal> {
+        let min_oracle_results = self.min_oracle_results;
+        let num_success = self.latest_confirmed_round.num_success;
+        require!(
+            num_success >= min_oracle_results,
+            PredictionMarketError::InvalidPriceFeed
+        );
+
+        Ok(self.latest_confirmed_round.result)
+    }
+
+    /// Fail unless the latest confirmed round opened no earlier than `start`
+    /// and at most `max_staleness` seconds after it
+    pub fn check_round_window(&self, start: i64, max_staleness: i64) -> Result<()> {
+        let round_open_timestamp = self.latest_confirmed_round.round_open_timestamp;
+        require!(
+            round_open_timestamp >= start && round_open_timestamp <= start.saturating_add(max_staleness),
+            PredictionMarketError::StaleOracleData
+        );
+
+        Ok(())
+    }
+}
@@ -1,7 +1,18 @@
-```rust
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Mint};
-use std::collections::BTreeMap;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::errors::PredictionMarketError;
+use crate::math::{apply_bps, mul_div_u64, Rounding};
+use crate::state::{Market, Oracle, Outcome};
+
+/// Maximum age of an oracle attestation, in seconds
+pub const MAX_ATTESTATION_AGE: i64 = 300;
+
+/// Allowed clock drift for attestations timestamped ahead of the cluster clock
+pub const MAX_ATTESTATION_CLOCK_SKEW: i64 = 30;
 
 /// Calculate betting odds based on total stakes
 pub fn calculate_odds(yes_stakes: u64, no_stakes: u64) -> Result<(u64, u64)> {
@@ -10,25 +21,36 @@ pub fn calculate_odds(yes_stakes: u64, no_stakes: u64) -> Result<(u64, u64)> {
     }
     
     let total_stakes = yes_stakes.checked_add(no_stakes)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
     
     if total_stakes == 0 {
         return Ok((5000, 5000));
     }
     
     // Calculate implied probability in basis points (0-10000)
-    let yes_probability = (yes_stakes as u128)
-        .checked_mul(10000)
-        .ok_or(ProgramError::ArithmeticOverflow)?
-        .checked_div(total_stakes as u128)
-        .ok_or(ProgramError::ArithmeticOverflow)? as u64;
+    let yes_probability = mul_div_u64(yes_stakes, 10000, total_stakes, Rounding::Down)?;
     
     let no_probability = 10000_u64.checked_sub(yes_probability)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
     
     Ok((yes_probability, no_probability))
 }
 
+/// Recalculate parimutuel odds for every outcome from its current stake
+pub fn recalculate_outcome_odds(outcomes: &mut [Outcome]) {
+    let total_market_pool: u64 = outcomes.iter().map(|o| o.total_amount).sum();
+    for outcome_data in outcomes.iter_mut() {
+        if outcome_data.total_amount == 0 && total_market_pool > 0 {
+            outcome_data.current_odds = u64::MAX; // unbounded until the outcome is staked
+        } else if total_market_pool > outcome_data.total_amount {
+            outcome_data.current_odds = mul_div_u64(total_market_pool, 10000, outcome_data.total_amount, Rounding::Down)
+                .unwrap_or(u64::MAX);
+        } else {
+            outcome_data.current_odds = 10000; // 1.0x odds
+        }
+    }
+}
+
 /// Calculate payout for a winning bet
 pub fn calculate_payout(
     bet_amount: u64,
@@ -41,26 +63,16 @@ pub fn calculate_payout(
     }
     
     let total_pool = winning_stakes.checked_add(losing_stakes)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
     
     // Calculate house edge
-    let house_fee = (total_pool as u128)
-        .checked_mul(house_edge_bps as u128)
-        .ok_or(ProgramError::ArithmeticOverflow)?
-        .checked_div(10000)
-        .ok_or(ProgramError::ArithmeticOverflow)? as u64;
+    let house_fee = apply_bps(total_pool, house_edge_bps, Rounding::Up)?;
     
     let net_pool = total_pool.checked_sub(house_fee)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
     
     // Calculate proportional payout
-    let payout = (bet_amount as u128)
-        .checked_mul(net_pool as u128)
-        .ok_or(ProgramError::ArithmeticOverflow)?
-        .checked_div(winning_stakes as u128)
-        .ok_or(ProgramError::ArithmeticOverflow)? as u64;
-    
-    Ok(payout)
+    mul_div_u64(bet_amount, net_pool, winning_stakes, Rounding::Down)
 }
 
 /// Calculate market liquidity score
@@ -91,7 +103,7 @@ pub fn validate_resolution_time(
     
     let max_resolution_time = market_end_time
         .checked_add(resolution_window)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
     
     Ok(current_timestamp <= max_resolution_time)
 }
@@ -111,23 +123,161 @@ pub fn calculate_min_bet_amount(
     min_bet_lamports.saturating_mul(scale_factor).min(1_000_000_000) // Cap at 1 SOL
 }
 
-/// Validate oracle signature for market resolution
+/// Message an oracle signs to attest a resolution:
+/// program id || market || outcome || timestamp (LE) || nonce (LE)
+pub fn oracle_attestation_message(market: &Pubkey, outcome: u8, timestamp: i64, nonce: u64) -> Vec<u8> {
+    let mut message = Vec::with_capacity(32 + 32 + 1 + 8 + 8);
+    message.extend_from_slice(&crate::ID.to_bytes());
+    message.extend_from_slice(&market.to_bytes());
+    message.push(outcome);
+    message.extend_from_slice(&timestamp.to_le_bytes());
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message
+}
+
+/// Validate oracle signature for market resolution.
+///
+/// The signature itself is checked by the Ed25519 program; this verifies the
+/// instruction immediately preceding the current one is an Ed25519 verify of
+/// the expected message by `oracle_pubkey`, and that the attestation is fresh.
+/// Nonce replay is checked by the caller against its stored nonce.
 pub fn validate_oracle_signature(
+    instructions_sysvar: &AccountInfo,
     oracle_pubkey: &Pubkey,
-    market_id: &Pubkey,
-    outcome: bool,
+    market: &Pubkey,
+    outcome: u8,
     timestamp: i64,
-    signature: &[u8; 64],
-) -> Result<bool> {
-    // Create message to verify
-    let mut message = Vec::new();
-    message.extend_from_slice(&market_id.to_bytes());
-    message.push(if outcome { 1 } else { 0 });
-    message.extend_from_slice(&timestamp.to_le_bytes());
-    
-    // In a real implementation, you would verify the signature here
-    // For now, we'll do basic validation
-    Ok(signature.len() == 64 && oracle_pubkey != &Pubkey::default())
+    nonce: u64,
+    current_time: i64,
+) -> Result<()> {
+    require!(
+        timestamp <= current_time.saturating_add(MAX_ATTESTATION_CLOCK_SKEW)
+            && current_time.saturating_sub(timestamp) <= MAX_ATTESTATION_AGE,
+        PredictionMarketError::SignatureExpired
+    );
+
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, PredictionMarketError::InvalidOracleSignature);
+    let ed25519_ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+    require!(
+        ed25519_ix.program_id == ed25519_program::ID && ed25519_ix.accounts.is_empty(),
+        PredictionMarketError::InvalidOracleSignature
+    );
+
+    // Ed25519 instruction data: count (u8), padding (u8), then one 14-byte
+    // offsets record per signature; all data must live in this instruction
+    let data = &ed25519_ix.data;
+    require!(data.len() >= 16 && data[0] == 1, PredictionMarketError::InvalidOracleSignature);
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let signature_instruction_index = read_u16(4);
+    let public_key_offset = read_u16(6) as usize;
+    let public_key_instruction_index = read_u16(8);
+    let message_data_offset = read_u16(10) as usize;
+    let message_data_size = read_u16(12) as usize;
+    let message_instruction_index = read_u16(14);
+    require!(
+        signature_instruction_index == u16::MAX
+            && public_key_instruction_index == u16::MAX
+            && message_instruction_index == u16::MAX,
+        PredictionMarketError::InvalidOracleSignature
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(PredictionMarketError::InvalidOracleSignature)?;
+    let message = data
+        .get(message_data_offset..message_data_offset + message_data_size)
+        .ok_or(PredictionMarketError::InvalidOracleSignature)?;
+
+    require!(
+        public_key == oracle_pubkey.as_ref()
+            && message == oracle_attestation_message(market, outcome, timestamp, nonce).as_slice(),
+        PredictionMarketError::InvalidOracleSignature
+    );
+
+    Ok(())
+}
+
+/// Trading fees owed on `amount` of collateral, as (creator, platform, oracle).
+/// Each fee is charged to the trader and so rounds up.
+pub fn calculate_trading_fees(market: &Market, amount: u64) -> Result<(u64, u64, u64)> {
+    let fee = |bps: u16| apply_bps(amount, bps as u64, Rounding::Up);
+
+    Ok((fee(market.creator_fee_bps)?, fee(market.platform_fee_bps)?, fee(market.oracle_fee_bps)?))
+}
+
+/// Credit fees paid into the market's protocol fee account to each party's
+/// withdrawable balance; returns their total
+pub fn accrue_fees(market: &mut Market, creator_fee: u64, platform_fee: u64, oracle_fee: u64) -> Result<u64> {
+    market.creator_fees_accrued = market.creator_fees_accrued
+        .checked_add(creator_fee)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    market.platform_fees_accrued = market.platform_fees_accrued
+        .checked_add(platform_fee)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    market.oracle_fees_accrued = market.oracle_fees_accrued
+        .checked_add(oracle_fee)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    creator_fee
+        .checked_add(platform_fee)
+        .and_then(|v| v.checked_add(oracle_fee))
+        .ok_or(PredictionMarketError::ArithmeticOverflow.into())
+}
+
+/// Registry entry of `oracle_authority`, or `None` if it never registered.
+/// `registry_info` must be its `[b"oracle", oracle_authority]` PDA.
+pub fn load_oracle_registry(registry_info: &AccountInfo, oracle_authority: &Pubkey) -> Result<Option<Oracle>> {
+    let (expected, _) = Pubkey::find_program_address(&[b"oracle", oracle_authority.as_ref()], &crate::ID);
+    require_keys_eq!(registry_info.key(), expected, PredictionMarketError::InvalidAccountOwner);
+
+    if registry_info.owner != &crate::ID || registry_info.data_is_empty() {
+        return Ok(None);
+    }
+
+    let data = registry_info.try_borrow_data()?;
+    Ok(Some(Oracle::try_deserialize(&mut &data[..])?))
+}
+
+/// Fail if `oracle_authority` is registered but its registry entry has been
+/// deactivated; unregistered oracles are accepted as before
+pub fn require_active_oracle(registry_info: &AccountInfo, oracle_authority: &Pubkey) -> Result<()> {
+    if let Some(oracle) = load_oracle_registry(registry_info, oracle_authority)? {
+        require!(oracle.is_active, PredictionMarketError::UnauthorizedOracle);
+    }
+
+    Ok(())
+}
+
+/// Record a finished resolution on the registry entry of `oracle_authority`;
+/// unregistered oracles have no track record to update
+pub fn record_oracle_resolution(registry_info: &AccountInfo, oracle_authority: &Pubkey, accurate: bool) -> Result<()> {
+    if let Some(mut oracle) = load_oracle_registry(registry_info, oracle_authority)? {
+        oracle.record_resolution(accurate);
+
+        let mut data = registry_info.try_borrow_mut_data()?;
+        oracle.try_serialize(&mut &mut data[..])?;
+    }
+
+    Ok(())
+}
+
+/// Rescale a fixed-point `value` from `from_exponent` to `to_exponent`,
+/// truncating toward zero when precision is dropped
+pub fn rescale_price(value: i64, from_exponent: i32, to_exponent: i32) -> Result<i128> {
+    let diff = from_exponent - to_exponent;
+    let factor = 10i128
+        .checked_pow(diff.unsigned_abs())
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    if diff >= 0 {
+        (value as i128)
+            .checked_mul(factor)
+            .ok_or(PredictionMarketError::ArithmeticOverflow.into())
+    } else {
+        Ok(value as i128 / factor)
+    }
 }
 
 /// Calculate market maker rewards
@@ -142,11 +292,11 @@ pub fn calculate_market_maker_rewards(
     
     let reward = (provided_liquidity as u128)
         .checked_mul(total_volume as u128)
-        .ok_or(ProgramError::ArithmeticOverflow)?
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?
         .checked_mul(reward_rate_bps as u128)
-        .ok_or(ProgramError::ArithmeticOverflow)?
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?
         .checked_div(10000 * 1_000_000_000) // Normalize to SOL
-        .ok_or(ProgramError::ArithmeticOverflow)? as u64;
+        .ok_or(PredictionMarketError::ArithmeticOverflow)? as u64;
     
     Ok(reward)
 }
@@ -247,26 +397,13 @@ pub fn validate_bet_constraints(
     market_cap: u64,
     current_stakes: u64,
 ) -> Result<()> {
-    require!(bet_amount >= min_bet, PredictionMarketError::BetTooSmall);
-    require!(bet_amount <= max_bet, PredictionMarketError::BetTooLarge);
-    require!(bet_amount <= user_balance, PredictionMarketError::InsufficientBalance);
+    require!(bet_amount >= min_bet, PredictionMarketError::BetBelowMinimum);
+    require!(bet_amount <= max_bet, PredictionMarketError::BetAboveMaximum);
+    require!(bet_amount <= user_balance, PredictionMarketError::InsufficientFunds);
     
     let new_total = current_stakes.checked_add(bet_amount)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    require!(new_total <= market_cap, PredictionMarketError::MarketCapExceeded);
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    require!(new_total <= market_cap, PredictionMarketError::MarketCapacityExceeded);
     
     Ok(())
 }
-
-#[error_code]
-pub enum PredictionMarketError {
-    #[msg("Bet amount is too small")]
-    BetTooSmall,
-    #[msg("Bet amount is too large")]
-    BetTooLarge,
-    #[msg("Insufficient balance")]
-    InsufficientBalance,
-    #[msg("Market cap exceeded")]
-    MarketCapExceeded,
-}
-```
\ No newline at end of file